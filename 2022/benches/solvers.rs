@@ -0,0 +1,97 @@
+//! Benchmarks the hot paths of each day on the checked-in inputs. Both day 3
+//! solutions are timed from the input text, so that the [`ItemSet`]s a
+//! rucksack is built with count against the scores they make quick.
+//!
+//! [`ItemSet`]: aoc::day3::item_set::ItemSet
+//!
+//! Run with `cargo bench`, or `cargo bench -- --json <PATH>` to also export the results.
+
+use std::{env, path::PathBuf, process, time::Duration};
+
+use aoc::{
+    day1, day2, day3,
+    error::Result,
+    input::Input,
+    timing::{self, Measurement},
+};
+
+const BUDGET: Duration = Duration::from_secs(1);
+const MIN_ITERATIONS: u32 = 10;
+
+fn load(day: u8) -> String {
+    Input::default().load(day).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    })
+}
+
+fn json_path() -> Option<PathBuf> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--json" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+fn main() {
+    let day1_input = load(1);
+    let day2_guide = day2::utils::parse_input(&load(2)).expect("day 2 input parses");
+    let day2_rules = day2::rules::Rules::default();
+    let day2_rounds = day2::cipher::Cipher::shapes(&day2_rules)
+        .decode(&day2_rules, &day2_guide)
+        .expect("day 2 input decodes");
+    let day3_input = load(3);
+
+    let measurements: Vec<Measurement> = vec![
+        timing::measure("day1::parse_input", BUDGET, MIN_ITERATIONS, || {
+            day1::parse_input(&day1_input)
+        }),
+        timing::measure(
+            "day2::utils::calculate_score",
+            BUDGET,
+            MIN_ITERATIONS,
+            || day2::utils::calculate_score(&day2_rules, &day2_rounds),
+        ),
+        timing::measure(
+            "day3::parse_input + calculate_total_rucksacks_score",
+            BUDGET,
+            MIN_ITERATIONS,
+            || -> Result<u64> {
+                let inventory = day3::parse_input(&day3_input)?;
+                day3::calculate_total_rucksacks_score(&inventory.rucksacks, &inventory.priorities)
+            },
+        ),
+        timing::measure(
+            "day3::strings::calculate_total_rucksacks_score",
+            BUDGET,
+            MIN_ITERATIONS,
+            || day3::strings::calculate_total_rucksacks_score(&day3_input),
+        ),
+        timing::measure(
+            "day3::parse_input + calculate_total_group_score",
+            BUDGET,
+            MIN_ITERATIONS,
+            || -> Result<u64> {
+                let inventory = day3::parse_input(&day3_input)?;
+                let groups = day3::create_rucksack_groups(&inventory.rucksacks)?;
+                day3::calculate_total_group_score(&groups, &inventory.priorities)
+            },
+        ),
+        timing::measure(
+            "day3::strings::calculate_total_group_score",
+            BUDGET,
+            MIN_ITERATIONS,
+            || day3::strings::calculate_total_group_score(&day3_input),
+        ),
+    ];
+
+    timing::print_table(&measurements);
+    if let Some(path) = json_path() {
+        if let Err(err) = timing::write_json(&path, &measurements) {
+            eprintln!("error: could not write `{}`: {}", path.display(), err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+    error::{AocError, Result},
+    solution::Part,
+};
+
+/// Answers for the checked-in inputs, written as `<day> <part> <answer>` lines,
+/// in the source tree the binary was built from.
+pub const DEFAULT_ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers> {
+        let content = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Answers::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Answers> {
+        let mut answers = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let malformed = || {
+                AocError::parse(
+                    index + 1,
+                    1,
+                    format!("expected `<day> <part> <answer>`, found `{}`", line),
+                )
+            };
+            let mut fields = line.split_whitespace();
+            let day = fields.next().and_then(|day| day.parse::<u8>().ok());
+            let part = match fields.next() {
+                Some("1") => Some(Part::One),
+                Some("2") => Some(Part::Two),
+                _ => None,
+            };
+            let answer = fields.next();
+            match (day, part, answer, fields.next()) {
+                (Some(day), Some(part), Some(answer), None) => {
+                    answers.insert((day, part), answer.to_string());
+                }
+                _ => return Err(malformed()),
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
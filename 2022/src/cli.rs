@@ -0,0 +1,932 @@
+use std::{fmt, path::PathBuf, slice};
+
+use crate::{
+    answers::DEFAULT_ANSWERS_PATH,
+    day1::generate::Distribution,
+    day2::{cipher::CipherOverride, crack::Goal, rules::Rules},
+    day3::{priorities::Priorities, Grouping, Remainder},
+    generate::Settings,
+    input::Input,
+    solution::{Options, Part},
+};
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: aoc <DAYS>... [--part <1|2>] [--input <PATH|->] [--inputs-dir <DIR>] [--n <N>] [--time] [--json <PATH>] [--verbose]
+           [--rules <NAME|PATH>] [--cipher <PATH>] [--opponent <MAP>] [--second[1|2] <MAP>]
+           [--group-size <K>] [--remainder <reject|drop|keep> | --windows] [--priorities <NAME|PATH>]
+       aoc --verify [DAYS]... [--answers <PATH>] [--verbose]
+       aoc 1 --stream [--part <1|2>] [--input <PATH|->] [--n <N>] [--verbose]
+       aoc stats [--input <PATH|->] [--buckets <N>] [--format <table|json>] [--verbose]
+       aoc tournament [GUIDE|-]... [--part <1|2>] [--progression] [--rules <NAME|PATH>] [--opponent <MAP>] [--second[1|2] <MAP>]
+       aoc analyse [--input <PATH|->] [--rules <NAME|PATH>] [--opponent <MAP>]
+       aoc crack [--input <PATH|->] [--max | --min | --target <SCORE>] [--rules <NAME|PATH>] [--opponent <MAP>] [--second <MAP>]
+       aoc repack [--input <PATH|->] [--priorities <NAME|PATH>] [--verbose]
+       aoc validate <DAY> [FILE|-] [--part <1|2>] [--verbose] [OPTIONS]...
+       aoc generate <DAY> [--seed <N>] [--size <N>] [--distribution <D>] [--output <PATH>] [--answers <PATH>]
+
+DAYS can be a single day (`2`), an inclusive range (`1..3`) or `all`.
+Without `--input`, each day reads `<DIR>/day<N>/input.txt`.
+`<DIR>` and the answers file default to the source tree the binary was built
+from, whatever the current directory.
+`--verify` checks every selected day (all by default) against the recorded answers.
+`--stream` solves day 1 in a single pass with bounded memory, for very large inputs.
+`stats` summarises the day 1 calorie inventories.
+`--rules` sets the day 2 game: `rock-paper-scissors` (the puzzle's),
+`lizard-spock`, or a file of `NAME SCORE` lines, one per shape, followed by
+`WINNER beats LOSER` lines or a `cyclic` line for the balanced game.
+A day 2 cipher gives the guide symbols their meaning: a TOML or JSON file with
+`opponent` and/or `second` tables, or `SYMBOL=MEANING` lists such as
+`--second X=Rock,Y=Paper,Z=Scissors`. The second column is replaced in both
+parts, unless a `part1.second` or `part2.second` table, or `--second1` or
+`--second2`, gives one part its own. Opponent symbols stand for shapes of the
+game; second-column symbols may also stand for Win, Draw or Loss. By default
+`A`, `B`, `C`... are the shapes in order, and `X`, `Y` and `Z` the first three
+or, in part 2, Loss, Draw and Win.
+`tournament` plays the day 2 guide, scoring both me and the opponent; given
+several guides of as many rounds, it plays the moves each tells me to make
+against one another.
+`analyse` measures how exploitable the day 2 opponent column is: the best
+possible score, a uniformly random reply, and frequency and Markov predictors.
+`crack` scores the day 2 guide under every reading of its three second-column
+symbols, as shapes or as results, and marks the best one. Both parts share
+these readings, so it takes `--second` but not `--second1` or `--second2`.
+Day 3 part 2 looks for the badge of each group of `--group-size` rucksacks.
+`--remainder` says what becomes of a short last group; `--windows` instead
+looks at every run of that many consecutive rucksacks.
+`repack` plans, for each day 3 rucksack with an item type in both compartments,
+the fewest moves that leave every type in a single compartment while keeping
+the compartment sizes.
+`--priorities` names the day 3 item types and their priorities: `letters`
+(the puzzle's), `alphanumeric`, `ascii`, `unicode` (any character, weighing
+its code point), or a file of `ITEM PRIORITY` lines, optionally starting with
+`extends NAME` to change or add to a built-in scheme.
+`validate` lists every way the input of DAY breaks the guarantees of its puzzle,
+with its line, and only solves it when there are none. It takes the options of
+the solvers.
+`generate` writes a random input for DAY, the same for the same seed: elves,
+guide rounds or rucksacks (in groups of three) as many as `--size`, and with
+`--answers` the answers to both parts, ready for `--verify --answers`.
+Inputs are cleaned up before parsing: a byte order mark, CRLF line endings and
+trailing whitespace are stripped, and runs of blank lines count as one separator,
+which only day 1 uses: days 2 and 3 skip blank lines.
+
+Options:
+  -p, --part <1|2>        Only run the given part
+  -i, --input <PATH|->    Read the input of a single day from PATH, or stdin for `-`
+      --inputs-dir <DIR>  Directory holding the per-day inputs [default: ",
+    env!("CARGO_MANIFEST_DIR"),
+    "/src]
+      --n <N>             Number of elves day 1 part 2 adds up [default: 3]
+      --rules <WHAT>      Day 2 game, built-in or from a file [default: rock-paper-scissors]
+      --cipher <PATH>     Read the day 2 guide with the cipher in PATH
+      --opponent <MAP>    Day 2 opponent symbols, e.g. `A=Rock,B=Paper,C=Scissors`
+      --second <MAP>      Day 2 second-column symbols, e.g. `X=Loss,Y=Draw,Z=Win`
+      --second1 <MAP>     Day 2 second-column symbols of part 1 only
+      --second2 <MAP>     Day 2 second-column symbols of part 2 only
+      --group-size <K>    Number of rucksacks in a day 3 group [default: 3]
+      --remainder <WHAT>  Reject, drop or keep a short last day 3 group [default: reject]
+      --windows           Group day 3 rucksacks in overlapping windows instead
+      --priorities <WHAT> Day 3 priority scheme, built-in or from a file [default: letters]
+      --progression       Print the running scores after every `tournament` round
+      --max, --min        Have `crack` pick the highest or lowest score [default: --max]
+      --target <SCORE>    Have `crack` pick the first reading scoring exactly SCORE
+      --stream            Read the day 1 input line by line instead of loading it whole
+      --time              Print how long loading, parsing and solving took
+      --json <PATH>       Also write the timings as JSON to PATH (implies --time)
+      --verify            Compare the answers with the recorded ones
+      --answers <PATH>    Recorded answers file [default: ",
+    env!("CARGO_MANIFEST_DIR"),
+    "/answers.txt], or where `generate` writes them
+      --seed <N>          Seed of `generate` [default: 0]
+      --size <N>          Number of elves, rounds or rucksacks `generate` writes
+      --distribution <D>  Calories of generated day 1 items: uniform, normal or exponential
+      --output <PATH>     Where `generate` writes the input [default: stdout]
+      --buckets <N>       Number of histogram buckets for `stats` [default: 10]
+      --format <FORMAT>   Output `table` or `json` [default: table]
+  -v, --verbose           Report every clean-up applied to the inputs
+  -h, --help              Print this message"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        days: Vec<u8>,
+        part: Option<Part>,
+        input: Input,
+        options: Options,
+        time: bool,
+        json: Option<PathBuf>,
+    },
+    Verify {
+        days: Vec<u8>,
+        part: Option<Part>,
+        input: Input,
+        options: Options,
+        answers: PathBuf,
+    },
+    Stream {
+        part: Option<Part>,
+        input: Input,
+        options: Options,
+    },
+    Stats {
+        input: Input,
+        buckets: usize,
+        format: Format,
+        verbose: bool,
+    },
+    Tournament {
+        guides: Vec<Input>,
+        part: Part,
+        progression: bool,
+        options: Options,
+    },
+    Analyse {
+        input: Input,
+        options: Options,
+    },
+    Crack {
+        input: Input,
+        goal: Goal,
+        options: Options,
+    },
+    Repack {
+        input: Input,
+        options: Options,
+    },
+    Validate {
+        day: u8,
+        part: Option<Part>,
+        input: Input,
+        options: Options,
+    },
+    Generate {
+        day: u8,
+        seed: u64,
+        settings: Settings,
+        output: Option<PathBuf>,
+        answers: Option<PathBuf>,
+    },
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    NoDays,
+    UnknownDay(String),
+    UnknownPart(String),
+    InvalidValue { option: String, value: String },
+    MissingValue(String),
+    UnknownOption(String),
+    InputForManyDays,
+    StreamNotSupported,
+    InvalidCipher(String),
+    InvalidRules(String),
+    InvalidPriorities(String),
+    ConflictingOptions(&'static str, &'static str),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::NoDays => write!(f, "no day given"),
+            CliError::UnknownDay(day) => write!(f, "unknown day `{}`", day),
+            CliError::UnknownPart(part) => write!(f, "unknown part `{}`, expected 1 or 2", part),
+            CliError::InvalidValue { option, value } => {
+                write!(f, "invalid value `{}` for `{}`", value, option)
+            }
+            CliError::MissingValue(option) => write!(f, "missing value for `{}`", option),
+            CliError::UnknownOption(option) => write!(f, "unknown option `{}`", option),
+            CliError::InputForManyDays => write!(f, "`--input` can only be used with a single day"),
+            CliError::StreamNotSupported => write!(f, "`--stream` is only supported for day 1"),
+            CliError::InvalidCipher(message) => write!(f, "invalid cipher: {}", message),
+            CliError::InvalidRules(message) => write!(f, "invalid rules: {}", message),
+            CliError::InvalidPriorities(message) => {
+                write!(f, "invalid priorities: {}", message)
+            }
+            CliError::ConflictingOptions(first, second) => {
+                write!(f, "`{}` cannot be combined with `{}`", first, second)
+            }
+        }
+    }
+}
+
+fn parse_day(day: &str, available: &[u8]) -> Result<u8, CliError> {
+    match day.parse::<u8>() {
+        Ok(day) if available.contains(&day) => Ok(day),
+        _ => Err(CliError::UnknownDay(day.to_string())),
+    }
+}
+
+fn parse_days(arg: &str, available: &[u8]) -> Result<Vec<u8>, CliError> {
+    if arg == "all" {
+        return Ok(available.to_vec());
+    }
+    match arg.split_once("..") {
+        Some((first, last)) => {
+            let first = parse_day(first, available)?;
+            let last = parse_day(last, available)?;
+            if first > last {
+                return Err(CliError::UnknownDay(arg.to_string()));
+            }
+            Ok(available
+                .iter()
+                .copied()
+                .filter(|day| (first..=last).contains(day))
+                .collect())
+        }
+        None => Ok(vec![parse_day(arg, available)?]),
+    }
+}
+
+fn parse_part(part: &str) -> Result<Part, CliError> {
+    match part {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError::UnknownPart(part.to_string())),
+    }
+}
+
+/// Walks the arguments, splitting `--option=value` so both spellings work.
+struct Args<'a> {
+    args: slice::Iter<'a, String>,
+    inline_value: Option<&'a str>,
+}
+
+impl<'a> Args<'a> {
+    fn new(args: &'a [String]) -> Args<'a> {
+        Args {
+            args: args.iter(),
+            inline_value: None,
+        }
+    }
+
+    fn next_arg(&mut self) -> Option<&'a str> {
+        let arg = self.args.next()?;
+        match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => {
+                self.inline_value = Some(value);
+                Some(option)
+            }
+            _ => {
+                self.inline_value = None;
+                Some(arg)
+            }
+        }
+    }
+
+    fn value(&mut self, option: &str) -> Result<&'a str, CliError> {
+        self.inline_value
+            .take()
+            .or_else(|| self.args.next().map(String::as_str))
+            .ok_or_else(|| CliError::MissingValue(option.to_string()))
+    }
+
+    /// Parses the value of `option` as a strictly positive number.
+    fn positive(&mut self, option: &str) -> Result<usize, CliError> {
+        let value = self.value(option)?;
+        match value.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(CliError::InvalidValue {
+                option: option.to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+}
+
+fn parse_remainder(option: &str, value: &str) -> Result<Remainder, CliError> {
+    match value {
+        "reject" => Ok(Remainder::Reject),
+        "drop" => Ok(Remainder::Drop),
+        "keep" => Ok(Remainder::Keep),
+        _ => Err(CliError::InvalidValue {
+            option: option.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn parse_format(option: &str, value: &str) -> Result<Format, CliError> {
+    match value {
+        "table" => Ok(Format::Table),
+        "json" => Ok(Format::Json),
+        _ => Err(CliError::InvalidValue {
+            option: option.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn parse_priorities(value: &str) -> Result<Priorities, CliError> {
+    Priorities::from_arg(value).map_err(|err| CliError::InvalidPriorities(err.to_string()))
+}
+
+/// The day 3 options, which only make a [`Grouping`] once all are read.
+#[derive(Default)]
+struct Day3Args {
+    group_size: Option<usize>,
+    remainder: Option<Remainder>,
+    windows: bool,
+}
+
+impl Day3Args {
+    /// Handles the day 3 options, returning whether `arg` was one.
+    fn parse(
+        &mut self,
+        arg: &str,
+        args: &mut Args,
+        options: &mut Options,
+    ) -> Result<bool, CliError> {
+        match arg {
+            "--group-size" => self.group_size = Some(args.positive(arg)?),
+            "--remainder" => self.remainder = Some(parse_remainder(arg, args.value(arg)?)?),
+            "--windows" => self.windows = true,
+            "--priorities" => options.priorities = parse_priorities(args.value(arg)?)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn grouping(&self) -> Result<Grouping, CliError> {
+        let size = self.group_size.unwrap_or(3);
+        match self.remainder {
+            Some(_) if self.windows => {
+                Err(CliError::ConflictingOptions("--remainder", "--windows"))
+            }
+            Some(remainder) => Ok(Grouping::Chunks { size, remainder }),
+            None if self.windows => Ok(Grouping::Windows { size }),
+            None => Ok(Grouping::Chunks {
+                size,
+                remainder: Remainder::Reject,
+            }),
+        }
+    }
+}
+
+/// Handles the day 2 game and cipher options, returning whether `arg` was one.
+fn parse_cipher_arg(arg: &str, args: &mut Args, options: &mut Options) -> Result<bool, CliError> {
+    match arg {
+        "--rules" => {
+            options.rules = Rules::from_arg(args.value(arg)?)
+                .map_err(|err| CliError::InvalidRules(err.to_string()))?;
+        }
+        "--cipher" => {
+            let cipher = CipherOverride::load(&PathBuf::from(args.value(arg)?))
+                .map_err(|err| CliError::InvalidCipher(err.to_string()))?;
+            options.cipher.merge(cipher);
+        }
+        "--opponent" | "--second" | "--second1" | "--second2" => {
+            let column = match arg {
+                "--second1" => "part1.second",
+                "--second2" => "part2.second",
+                _ => &arg[2..],
+            };
+            let mappings = args.value(arg)?;
+            options
+                .cipher
+                .set_column(column, mappings)
+                .map_err(|err| CliError::InvalidCipher(err.to_string()))?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Checks, once every option is read, that the cipher only names shapes of
+/// the game.
+fn check_cipher(options: &Options) -> Result<(), CliError> {
+    options
+        .cipher
+        .check_shapes(&options.rules)
+        .map_err(|err| CliError::InvalidCipher(err.to_string()))
+}
+
+fn parse_tournament_args(args: &[String]) -> Result<Command, CliError> {
+    let mut guides = Vec::new();
+    let mut part = Part::One;
+    let mut progression = false;
+    let mut options = Options::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-p" | "--part" => part = parse_part(args.value(arg)?)?,
+            "--progression" => progression = true,
+            "-v" | "--verbose" => options.verbose = true,
+            _ if parse_cipher_arg(arg, &mut args, &mut options)? => {}
+            "-" => guides.push(Input::Stdin),
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg.to_string())),
+            _ => guides.push(Input::from_arg(arg)),
+        }
+    }
+    check_cipher(&options)?;
+    Ok(Command::Tournament {
+        guides,
+        part,
+        progression,
+        options,
+    })
+}
+
+fn parse_analyse_args(args: &[String]) -> Result<Command, CliError> {
+    let mut input = Input::default();
+    let mut options = Options::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => input = Input::from_arg(args.value(arg)?),
+            "--inputs-dir" => input = Input::Directory(PathBuf::from(args.value(arg)?)),
+            "-v" | "--verbose" => options.verbose = true,
+            _ if parse_cipher_arg(arg, &mut args, &mut options)? => {}
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    check_cipher(&options)?;
+    Ok(Command::Analyse { input, options })
+}
+
+fn parse_crack_args(args: &[String]) -> Result<Command, CliError> {
+    let mut input = Input::default();
+    let mut goal = Goal::Max;
+    let mut options = Options::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => input = Input::from_arg(args.value(arg)?),
+            "--inputs-dir" => input = Input::Directory(PathBuf::from(args.value(arg)?)),
+            "--max" => goal = Goal::Max,
+            "--min" => goal = Goal::Min,
+            "--target" => {
+                let value = args.value(arg)?;
+                let target = value.parse::<u64>().map_err(|_| CliError::InvalidValue {
+                    option: arg.to_string(),
+                    value: value.to_string(),
+                })?;
+                goal = Goal::Target(target);
+            }
+            "-v" | "--verbose" => options.verbose = true,
+            // Every reading is tried for both parts alike.
+            "--second1" | "--second2" => return Err(CliError::UnknownOption(arg.to_string())),
+            _ if parse_cipher_arg(arg, &mut args, &mut options)? => {}
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    check_cipher(&options)?;
+    Ok(Command::Crack {
+        input,
+        goal,
+        options,
+    })
+}
+
+fn parse_stats_args(args: &[String]) -> Result<Command, CliError> {
+    let mut input = Input::default();
+    let mut buckets = 10;
+    let mut format = Format::Table;
+    let mut verbose = false;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => input = Input::from_arg(args.value(arg)?),
+            "--inputs-dir" => input = Input::Directory(PathBuf::from(args.value(arg)?)),
+            "--buckets" => buckets = args.positive(arg)?,
+            "--format" => format = parse_format(arg, args.value(arg)?)?,
+            "-v" | "--verbose" => verbose = true,
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    Ok(Command::Stats {
+        input,
+        buckets,
+        format,
+        verbose,
+    })
+}
+
+fn parse_repack_args(args: &[String]) -> Result<Command, CliError> {
+    let mut input = Input::default();
+    let mut options = Options::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-i" | "--input" => input = Input::from_arg(args.value(arg)?),
+            "--inputs-dir" => input = Input::Directory(PathBuf::from(args.value(arg)?)),
+            "--priorities" => options.priorities = parse_priorities(args.value(arg)?)?,
+            "-v" | "--verbose" => options.verbose = true,
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    Ok(Command::Repack { input, options })
+}
+
+fn parse_generate_args(args: &[String], available: &[u8]) -> Result<Command, CliError> {
+    let mut day = None;
+    let mut seed = 0;
+    let mut settings = Settings::default();
+    let mut output = None;
+    let mut answers = None;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--seed" => {
+                let value = args.value(arg)?;
+                seed = value.parse().map_err(|_| CliError::InvalidValue {
+                    option: arg.to_string(),
+                    value: value.to_string(),
+                })?;
+            }
+            "--size" => settings.size = Some(args.positive(arg)?),
+            "--distribution" => {
+                let value = args.value(arg)?;
+                settings.distribution =
+                    Distribution::parse(value).ok_or_else(|| CliError::InvalidValue {
+                        option: arg.to_string(),
+                        value: value.to_string(),
+                    })?;
+            }
+            "-o" | "--output" => output = Some(PathBuf::from(args.value(arg)?)),
+            "--answers" => answers = Some(PathBuf::from(args.value(arg)?)),
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg.to_string())),
+            _ if day.is_none() => day = Some(parse_day(arg, available)?),
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    Ok(Command::Generate {
+        day: day.ok_or(CliError::NoDays)?,
+        seed,
+        settings,
+        output,
+        answers,
+    })
+}
+
+fn parse_validate_args(args: &[String], available: &[u8]) -> Result<Command, CliError> {
+    let mut day = None;
+    let mut file = None;
+    let mut inputs_dir = None;
+    let mut part = None;
+    let mut options = Options::default();
+    let mut day3_args = Day3Args::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-p" | "--part" => part = Some(parse_part(args.value(arg)?)?),
+            "--inputs-dir" => inputs_dir = Some(PathBuf::from(args.value(arg)?)),
+            "--n" => options.top_n = args.positive(arg)?,
+            "-v" | "--verbose" => options.verbose = true,
+            _ if parse_cipher_arg(arg, &mut args, &mut options)? => {}
+            _ if day3_args.parse(arg, &mut args, &mut options)? => {}
+            "-" if day.is_some() && file.is_none() => file = Some(Input::Stdin),
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg.to_string())),
+            _ if day.is_none() => day = Some(parse_day(arg, available)?),
+            _ if file.is_none() => file = Some(Input::from_arg(arg)),
+            _ => return Err(CliError::UnknownOption(arg.to_string())),
+        }
+    }
+    options.grouping = day3_args.grouping()?;
+    check_cipher(&options)?;
+
+    let day = day.ok_or(CliError::NoDays)?;
+    let input = match (file, inputs_dir) {
+        (Some(file), _) => file,
+        (None, Some(dir)) => Input::Directory(dir),
+        (None, None) => Input::default(),
+    };
+    Ok(Command::Validate {
+        day,
+        part,
+        input,
+        options,
+    })
+}
+
+fn parse_run_args(args: &[String], available: &[u8]) -> Result<Command, CliError> {
+    let mut days: Vec<u8> = Vec::new();
+    let mut part = None;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut options = Options::default();
+    let mut time = false;
+    let mut json = None;
+    let mut verify = false;
+    let mut stream = false;
+    let mut answers = None;
+    let mut day3_args = Day3Args::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-p" | "--part" => part = Some(parse_part(args.value(arg)?)?),
+            "-i" | "--input" => input = Some(Input::from_arg(args.value(arg)?)),
+            "--inputs-dir" => inputs_dir = Some(PathBuf::from(args.value(arg)?)),
+            "--n" => options.top_n = args.positive(arg)?,
+            "--time" => time = true,
+            "--json" => json = Some(PathBuf::from(args.value(arg)?)),
+            "--verify" => verify = true,
+            "--stream" => stream = true,
+            "-v" | "--verbose" => options.verbose = true,
+            "--answers" => answers = Some(PathBuf::from(args.value(arg)?)),
+            _ if parse_cipher_arg(arg, &mut args, &mut options)? => {}
+            _ if day3_args.parse(arg, &mut args, &mut options)? => {}
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg.to_string())),
+            _ => {
+                for day in parse_days(arg, available)? {
+                    if !days.contains(&day) {
+                        days.push(day);
+                    }
+                }
+            }
+        }
+    }
+
+    options.grouping = day3_args.grouping()?;
+    check_cipher(&options)?;
+
+    if days.is_empty() {
+        if !verify {
+            return Err(CliError::NoDays);
+        }
+        days = available.to_vec();
+    }
+    let input = match (input, inputs_dir) {
+        (Some(_), _) if days.len() > 1 => return Err(CliError::InputForManyDays),
+        (Some(input), _) => input,
+        (None, Some(dir)) => Input::Directory(dir),
+        (None, None) => Input::default(),
+    };
+    if stream {
+        if days != [1] || verify {
+            return Err(CliError::StreamNotSupported);
+        }
+        if json.is_some() {
+            return Err(CliError::ConflictingOptions("--stream", "--json"));
+        }
+        if time {
+            return Err(CliError::ConflictingOptions("--stream", "--time"));
+        }
+        return Ok(Command::Stream {
+            part,
+            input,
+            options,
+        });
+    }
+    if verify {
+        let answers = answers.unwrap_or_else(|| PathBuf::from(DEFAULT_ANSWERS_PATH));
+        return Ok(Command::Verify {
+            days,
+            part,
+            input,
+            options,
+            answers,
+        });
+    }
+    Ok(Command::Run {
+        days,
+        part,
+        input,
+        options,
+        time: time || json.is_some(),
+        json,
+    })
+}
+
+pub fn parse_args(args: &[String], available: &[u8]) -> Result<Command, CliError> {
+    match args.first().map(String::as_str) {
+        Some("stats") => parse_stats_args(&args[1..]),
+        Some("tournament") => parse_tournament_args(&args[1..]),
+        Some("analyse") => parse_analyse_args(&args[1..]),
+        Some("crack") => parse_crack_args(&args[1..]),
+        Some("repack") => parse_repack_args(&args[1..]),
+        Some("validate") => parse_validate_args(&args[1..], available),
+        Some("generate") => parse_generate_args(&args[1..], available),
+        _ => parse_run_args(args, available),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        parse_args(&args, &[1, 2, 3])
+    }
+
+    #[test]
+    fn parse_args_expands_day_ranges() {
+        match parse("1..2 3 --part=2").unwrap() {
+            Command::Run { days, part, .. } => {
+                assert_eq!(days, vec![1, 2, 3]);
+                assert_eq!(part, Some(Part::Two));
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn parse_args_rejects_unknown_days_and_parts() {
+        assert_eq!(parse("4"), Err(CliError::UnknownDay("4".to_string())));
+        assert_eq!(parse("1 -p 3"), Err(CliError::UnknownPart("3".to_string())));
+        assert_eq!(parse("--part 1"), Err(CliError::NoDays));
+        assert_eq!(parse("all -i x"), Err(CliError::InputForManyDays));
+        assert_eq!(parse("2 --stream"), Err(CliError::StreamNotSupported));
+        assert_eq!(
+            parse("1 --time --stream"),
+            Err(CliError::ConflictingOptions("--stream", "--time"))
+        );
+        assert_eq!(
+            parse("1 --stream --json t.json"),
+            Err(CliError::ConflictingOptions("--stream", "--json"))
+        );
+        assert!(matches!(
+            parse("2 --second X=Lizard"),
+            Err(CliError::InvalidCipher(_))
+        ));
+    }
+
+    #[test]
+    fn parse_args_reads_day2_rules() {
+        match parse("2 --rules lizard-spock --second X=Lizard,Y=Spock,Z=Rock").unwrap() {
+            Command::Run { options, .. } => {
+                assert_eq!(options.rules, Rules::rock_paper_scissors_lizard_spock())
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+        match parse("2 --second X=Win --second2 X=Loss").unwrap() {
+            Command::Run { options, .. } => {
+                assert_eq!(options.cipher.part1_second, None);
+                assert!(options.cipher.part2_second.is_some());
+                assert_ne!(
+                    options.cipher.second(Part::One),
+                    options.cipher.second(Part::Two)
+                );
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+        assert!(matches!(
+            parse("crack --rules does/not/exist.txt"),
+            Err(CliError::InvalidRules(_))
+        ));
+        assert!(matches!(
+            parse("analyse --opponent A=Spock"),
+            Err(CliError::InvalidCipher(_))
+        ));
+    }
+
+    #[test]
+    fn parse_args_reads_day3_grouping() {
+        let grouping = |args| match parse(args).unwrap() {
+            Command::Run { options, .. } => options.grouping,
+            command => panic!("unexpected command {:?}", command),
+        };
+        assert_eq!(grouping("3"), Grouping::default());
+        assert_eq!(
+            grouping("3 --group-size 4 --remainder=keep"),
+            Grouping::Chunks {
+                size: 4,
+                remainder: Remainder::Keep
+            }
+        );
+        assert_eq!(
+            grouping("3 --windows --group-size 2"),
+            Grouping::Windows { size: 2 }
+        );
+        assert_eq!(
+            parse("3 --windows --remainder drop"),
+            Err(CliError::ConflictingOptions("--remainder", "--windows"))
+        );
+        assert!(parse("3 --remainder some").is_err());
+    }
+
+    #[test]
+    fn parse_args_reads_validate_day_and_file() {
+        assert_eq!(
+            parse("validate 3 rucksacks.txt --windows").unwrap(),
+            Command::Validate {
+                day: 3,
+                part: None,
+                input: Input::from_arg("rucksacks.txt"),
+                options: Options {
+                    grouping: Grouping::Windows { size: 3 },
+                    ..Options::default()
+                },
+            }
+        );
+        match parse("validate 2 - -p 1").unwrap() {
+            Command::Validate { day, input, .. } => assert_eq!((day, input), (2, Input::Stdin)),
+            command => panic!("unexpected command {:?}", command),
+        }
+        assert_eq!(parse("validate"), Err(CliError::NoDays));
+        assert_eq!(
+            parse("validate 4"),
+            Err(CliError::UnknownDay("4".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_args_reads_generate_settings() {
+        assert_eq!(
+            parse("generate 1 --seed 0 --size=10 --distribution normal --answers a.txt").unwrap(),
+            Command::Generate {
+                day: 1,
+                seed: 0,
+                settings: Settings {
+                    size: Some(10),
+                    distribution: Distribution::Normal,
+                },
+                output: None,
+                answers: Some(PathBuf::from("a.txt")),
+            }
+        );
+        assert!(matches!(
+            parse("generate 1 --seed -1"),
+            Err(CliError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse("generate 1 --distribution zipf"),
+            Err(CliError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_args_reads_priorities() {
+        match parse("3 --priorities ascii").unwrap() {
+            Command::Run { options, .. } => assert_eq!(options.priorities, Priorities::ascii()),
+            command => panic!("unexpected command {:?}", command),
+        }
+        assert!(matches!(
+            parse("3 --priorities does/not/exist.txt"),
+            Err(CliError::InvalidPriorities(_))
+        ));
+        match parse("repack --priorities=unicode -i -").unwrap() {
+            Command::Repack { input, options } => {
+                assert_eq!(input, Input::Stdin);
+                assert_eq!(options.priorities, Priorities::unicode());
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn parse_args_reads_tournament_guides() {
+        match parse("tournament a.txt - --part 2").unwrap() {
+            Command::Tournament { guides, part, .. } => {
+                assert_eq!(guides, vec![Input::from_arg("a.txt"), Input::Stdin]);
+                assert_eq!(part, Part::Two);
+            }
+            command => panic!("unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn parse_args_reads_crack_goal() {
+        match parse("crack --min --target=15").unwrap() {
+            Command::Crack { goal, .. } => assert_eq!(goal, Goal::Target(15)),
+            command => panic!("unexpected command {:?}", command),
+        }
+        assert!(parse("crack --second L=Rock,D=Paper,W=Scissors").is_ok());
+        assert!(matches!(
+            parse("crack --second2 X=Win"),
+            Err(CliError::UnknownOption(option)) if option == "--second2"
+        ));
+    }
+
+    #[test]
+    fn parse_args_reads_stats_options() {
+        assert_eq!(
+            parse("stats --buckets 5 --format json -v").unwrap(),
+            Command::Stats {
+                input: Input::default(),
+                buckets: 5,
+                format: Format::Json,
+                verbose: true,
+            }
+        );
+        assert!(matches!(
+            parse("stats --buckets 0"),
+            Err(CliError::InvalidValue { .. })
+        ));
+    }
+}
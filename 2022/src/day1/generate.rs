@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use crate::{
+    error::{AocError, Result},
+    generate::Generated,
+    rng::Rng,
+};
+
+/// As many elves as in the checked-in input.
+pub const DEFAULT_ELVES: usize = 250;
+/// The most food items a generated elf carries.
+const MAX_ITEMS: u64 = 12;
+/// The mean calories of a food item, whatever the distribution.
+const MEAN_CALORIES: f64 = 5000.0;
+
+/// How the calories of the food items are spread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Distribution {
+    /// Anywhere from 1 to 9999.
+    #[default]
+    Uniform,
+    /// Bunched around the mean, with a standard deviation of 1500.
+    Normal,
+    /// Mostly small, with a long tail of large items.
+    Exponential,
+}
+
+impl Distribution {
+    pub fn parse(name: &str) -> Option<Distribution> {
+        match name {
+            "uniform" => Some(Distribution::Uniform),
+            "normal" => Some(Distribution::Normal),
+            "exponential" => Some(Distribution::Exponential),
+            _ => None,
+        }
+    }
+
+    fn sample(&self, rng: &mut Rng) -> u64 {
+        let calories = match self {
+            Distribution::Uniform => return rng.between(1, 2 * MEAN_CALORIES as u64 - 1),
+            Distribution::Normal => {
+                // The sum of twelve uniform draws, less six, is close to a
+                // standard normal draw.
+                let z: f64 = (0..12).map(|_| rng.unit()).sum::<f64>() - 6.0;
+                MEAN_CALORIES + 1500.0 * z
+            }
+            Distribution::Exponential => -(1.0 - rng.unit()).ln() * MEAN_CALORIES,
+        };
+        (calories.round() as u64).max(1)
+    }
+}
+
+/// An inventory of `elves` elves, each with 1 to 12 food items. The answers
+/// are the largest total and the sum of the three largest.
+pub fn generate(rng: &mut Rng, elves: usize, distribution: Distribution) -> Result<Generated> {
+    if elves < 3 {
+        return Err(AocError::invalid_data(
+            "day 1 needs at least three elves for part 2",
+        ));
+    }
+    let mut input = String::new();
+    let mut totals = Vec::with_capacity(elves);
+    for elf in 0..elves {
+        if elf > 0 {
+            input.push('\n');
+        }
+        let mut total = 0;
+        for _ in 0..rng.between(1, MAX_ITEMS) {
+            let calories = distribution.sample(rng);
+            total += calories;
+            writeln!(input, "{}", calories).expect("writing to a string cannot fail");
+        }
+        totals.push(total);
+    }
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(Generated {
+        input,
+        answers: [totals[0], totals[..3].iter().sum()],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::{parse_input, puzzle_1, puzzle_2};
+
+    #[test]
+    fn generated_answers_match_the_solver() {
+        for distribution in [
+            Distribution::Uniform,
+            Distribution::Normal,
+            Distribution::Exponential,
+        ] {
+            let generated = generate(&mut Rng::new(3), 40, distribution).unwrap();
+            let elves = parse_input(&generated.input).unwrap();
+            assert_eq!(elves.len(), 40);
+            assert_eq!(puzzle_1(&elves).unwrap(), generated.answers[0]);
+            assert_eq!(puzzle_2(&elves, 3).unwrap(), generated.answers[1]);
+        }
+        assert!(generate(&mut Rng::new(3), 2, Distribution::Uniform).is_err());
+    }
+}
@@ -1,153 +1,349 @@
-use std::{collections::HashMap, fs};
+pub mod generate;
+pub mod stats;
+pub mod stream;
+mod top_n;
+
+use crate::{
+    error::{checked_sum, AocError, Result},
+    solution::Solution,
+};
+
+pub use crate::day1::top_n::{top_n, RankedElve, TopN};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct Elve {
-    index: i32,
-    calories_entries: Vec<i32>,
+pub struct Elve {
+    index: usize,
+    calories_entries: Vec<u64>,
 }
 
 impl Elve {
-    fn new(index: i32) -> Elve {
+    fn new(index: usize) -> Elve {
         Elve {
             index,
             calories_entries: Vec::new(),
         }
     }
 
-    fn add_calories(&mut self, calories: i32) {
+    fn add_calories(&mut self, calories: u64) {
         self.calories_entries.push(calories);
     }
 
-    fn get_calories(&self) -> i32 {
-        let mut sum = 0;
-        for calories in &self.calories_entries {
-            sum += calories;
-        }
-        sum
+    fn get_calories(&self) -> Result<u64> {
+        checked_sum(
+            &format!("the calories carried by elf {}", self.index),
+            self.calories_entries.iter().copied(),
+        )
+    }
+}
+
+fn parse_calories(line_number: usize, line: &str) -> Result<u64> {
+    if line.starts_with('-') {
+        return Err(AocError::parse(
+            line_number,
+            1,
+            format!("negative calories `{}`", line),
+        ));
     }
+    line.parse::<u64>().map_err(|err| {
+        AocError::parse(
+            line_number,
+            1,
+            format!("invalid calories `{}`: {}", line, err),
+        )
+    })
 }
 
-fn parse_input() -> Vec<Elve> {
-    // read file
-    let content =
-        fs::read_to_string("src/day1/input.txt").expect("Something went wrong reading the file");
+/// What every day 1 answer and report says of an input without a single
+/// calorie line.
+fn no_elves() -> AocError {
+    AocError::invalid_data("there are no elves")
+}
 
+pub fn parse_input(content: &str) -> Result<Vec<Elve>> {
     let mut elves: Vec<Elve> = Vec::new();
 
     let mut elve_count = 1;
     let mut current_elve = Elve::new(elve_count);
-    for line in content.lines() {
-        if line != "" {
-            current_elve.add_calories(line.parse::<i32>().unwrap());
+    for (index, line) in content.lines().enumerate() {
+        if !line.is_empty() {
+            current_elve.add_calories(parse_calories(index + 1, line)?);
         } else {
             elves.push(current_elve.clone());
             elve_count += 1;
             current_elve = Elve::new(elve_count);
         }
     }
-    elves.push(current_elve.clone());
+    // Blank input has no last elf to close.
+    if !current_elve.calories_entries.is_empty() {
+        elves.push(current_elve);
+    }
 
-    elves
+    Ok(elves)
 }
 
-fn puzzle_1() {
-    let elves = parse_input();
-    let elve_with_most_calories = elves.iter().max_by_key(|elve| elve.get_calories()).unwrap();
+/// Checks that every line is a non-negative number of calories and that
+/// no elf carries more than fits in 64 bits, reporting every offence.
+pub fn validate(content: &str) -> Vec<AocError> {
+    let mut violations = Vec::new();
+    // The first line of the current elf, and its total until that overflows.
+    let mut elf: Option<(usize, Option<u64>)> = None;
+    for (index, line) in content.lines().enumerate() {
+        if line.is_empty() {
+            elf = None;
+            continue;
+        }
+        let (first_line, total) = elf.get_or_insert((index + 1, Some(0)));
+        match parse_calories(index + 1, line) {
+            Ok(calories) => {
+                if let Some(sum) = *total {
+                    *total = sum.checked_add(calories);
+                    if total.is_none() {
+                        violations.push(AocError::parse(
+                            *first_line,
+                            1,
+                            "the calories of this elf do not fit in 64 bits",
+                        ));
+                    }
+                }
+            }
+            Err(err) => violations.push(err),
+        }
+    }
+    if content.trim().is_empty() {
+        violations.push(no_elves());
+    }
+    violations
+}
 
-    println!(
-        "Elve with most calories: {:?}, total_calories: {:?}",
-        elve_with_most_calories,
-        elve_with_most_calories.get_calories()
-    );
-    // Elve with most calories: Elve { index: 127, calories_entries: [1596, 5306, 5192, 7398, 7306, 7457, 2157, 4592, 7133, 5658, 6134, 5000] }, total_calories: 64929
+fn puzzle_1(elves: &[Elve]) -> Result<u64> {
+    top_n(elves, 1)?
+        .first()
+        .map(|elve| elve.calories)
+        .ok_or_else(no_elves)
 }
 
-fn puzzle_2() {
-    let elves = parse_input();
-    let mut top_elves: Vec<Elve> = Vec::new();
-    while top_elves.len() < 3 {
-        let elve_with_most_calories = elves
-            .iter()
-            .filter(|elve| !top_elves.contains(elve))
-            .max_by_key(|elve| elve.get_calories())
-            .unwrap();
-        top_elves.push(elve_with_most_calories.clone());
-    }
-    let top_elves_total_calories = top_elves
-        .iter()
-        .fold(0, |acc, elve| acc + elve.get_calories());
-    println!(
-        "Top 3 elves with most calories: {:?}, total_calories: {:?}",
-        top_elves, top_elves_total_calories
-    );
-    // Elves with most calories: [Elve { index: 127, calories_entries: [1596, 5306, 5192, 7398, 7306, 7457, 2157, 4592, 7133, 5658, 6134, 5000] }, Elve { index: 112, calories_entries: [36692, 27998] }, Elve { index: 203, calories_entries: [64078] }], total_calories: 193697
+/// Adds up the best `n` of `elves` elves, failing when there are fewer than `n`.
+fn sum_top_n(top_elves: &[RankedElve], n: usize, elves: usize) -> Result<u64> {
+    if elves == 0 {
+        return Err(no_elves());
+    }
+    if top_elves.len() < n {
+        return Err(AocError::invalid_data(format!(
+            "expected at least {} elves, found {}",
+            n, elves
+        )));
+    }
+    checked_sum(
+        &format!("the calories of the top {} elves", n),
+        top_elves.iter().take(n).map(|elve| elve.calories),
+    )
+}
+
+fn puzzle_2(elves: &[Elve], n: usize) -> Result<u64> {
+    sum_top_n(&top_n(elves, n)?, n, elves.len())
+}
+
+// --- Day 1: Calorie Counting ---
+//
+// Santa's reindeer typically eat regular reindeer food, but they need a lot of magical energy to deliver presents on Christmas.
+// For that, their favorite snack is a special type of star fruit that only grows deep in the jungle.
+// The Elves have brought you on their annual expedition to the grove where the fruit grows.
+//
+// To supply enough magical energy, the expedition needs to retrieve a minimum of fifty stars by December 25th.
+// Although the Elves assure you that the grove has plenty of fruit, you decide to grab any fruit you see along the way, just in case.
+//
+// Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar;
+// the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+//
+// The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; the Elves' expedition traditionally goes on foot.
+// As your boats approach land, the Elves begin taking inventory of their supplies.
+// One important consideration is food - in particular, the number of Calories each Elf is carrying (your puzzle input).
+//
+// The Elves take turns writing down the number of Calories contained by the various meals, snacks, rations, etc. that they've brought with them, one item per line.
+// Each Elf separates their own inventory from the previous Elf's inventory (if any) by a blank line.
+//
+// For example, suppose the Elves finish writing their items' Calories and end up with the following list:
+//
+// 1000
+// 2000
+// 3000
+//
+// 4000
+//
+// 5000
+// 6000
+//
+// 7000
+// 8000
+// 9000
+//
+// 10000
+//
+// This list represents the Calories of the food carried by five Elves:
+//
+//     The first Elf is carrying food with 1000, 2000, and 3000 Calories, a total of 6000 Calories.
+//     The second Elf is carrying one food item with 4000 Calories.
+//     The third Elf is carrying food with 5000 and 6000 Calories, a total of 11000 Calories.
+//     The fourth Elf is carrying food with 7000, 8000, and 9000 Calories, a total of 24000 Calories.
+//     The fifth Elf is carrying one food item with 10000 Calories.
+//
+// In case the Elves get hungry and need extra snacks, they need to know which Elf to ask:
+// they'd like to know how many Calories are being carried by the Elf carrying the most Calories.
+// In the example above, this is 24000 (carried by the fourth Elf).
+//
+// Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
+//
+// Your puzzle answer was 64929.
+// --- Part Two ---
+//
+// By the time you calculate the answer to the Elves' question, they've already realized that the Elf carrying the most Calories of food might eventually run out of snacks.
+//
+// To avoid this unacceptable situation, the Elves would instead like to know the total Calories carried by the top three Elves carrying the most Calories.
+// That way, even if one of those Elves runs out of snacks, they still have two backups.
+//
+// In the example above, the top three Elves are the fourth Elf (with 24000 Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with 10000 Calories).
+// The sum of the Calories carried by these three elves is 45000.
+//
+// Find the top three Elves carrying the most Calories. How many Calories are those Elves carrying in total?
+//
+// Your puzzle answer was 193697.
+pub struct Day1 {
+    /// How many of the best-stocked elves part 2 adds up.
+    pub top_n: usize,
+}
+
+impl Default for Day1 {
+    fn default() -> Day1 {
+        Day1 { top_n: 3 }
+    }
 }
 
-pub(crate) fn run() {
-    // --- Day 1: Calorie Counting ---
-    //
-    // Santa's reindeer typically eat regular reindeer food, but they need a lot of magical energy to deliver presents on Christmas.
-    // For that, their favorite snack is a special type of star fruit that only grows deep in the jungle.
-    // The Elves have brought you on their annual expedition to the grove where the fruit grows.
-    //
-    // To supply enough magical energy, the expedition needs to retrieve a minimum of fifty stars by December 25th.
-    // Although the Elves assure you that the grove has plenty of fruit, you decide to grab any fruit you see along the way, just in case.
-    //
-    // Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar;
-    // the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
-    //
-    // The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; the Elves' expedition traditionally goes on foot.
-    // As your boats approach land, the Elves begin taking inventory of their supplies.
-    // One important consideration is food - in particular, the number of Calories each Elf is carrying (your puzzle input).
-    //
-    // The Elves take turns writing down the number of Calories contained by the various meals, snacks, rations, etc. that they've brought with them, one item per line.
-    // Each Elf separates their own inventory from the previous Elf's inventory (if any) by a blank line.
-    //
-    // For example, suppose the Elves finish writing their items' Calories and end up with the following list:
-    //
-    // 1000
-    // 2000
-    // 3000
-    //
-    // 4000
-    //
-    // 5000
-    // 6000
-    //
-    // 7000
-    // 8000
-    // 9000
-    //
-    // 10000
-    //
-    // This list represents the Calories of the food carried by five Elves:
-    //
-    //     The first Elf is carrying food with 1000, 2000, and 3000 Calories, a total of 6000 Calories.
-    //     The second Elf is carrying one food item with 4000 Calories.
-    //     The third Elf is carrying food with 5000 and 6000 Calories, a total of 11000 Calories.
-    //     The fourth Elf is carrying food with 7000, 8000, and 9000 Calories, a total of 24000 Calories.
-    //     The fifth Elf is carrying one food item with 10000 Calories.
-    //
-    // In case the Elves get hungry and need extra snacks, they need to know which Elf to ask:
-    // they'd like to know how many Calories are being carried by the Elf carrying the most Calories.
-    // In the example above, this is 24000 (carried by the fourth Elf).
-    //
-    // Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
-    //
-    // Your puzzle answer was 64929.
-    // --- Part Two ---
-    //
-    // By the time you calculate the answer to the Elves' question, they've already realized that the Elf carrying the most Calories of food might eventually run out of snacks.
-    //
-    // To avoid this unacceptable situation, the Elves would instead like to know the total Calories carried by the top three Elves carrying the most Calories.
-    // That way, even if one of those Elves runs out of snacks, they still have two backups.
-    //
-    // In the example above, the top three Elves are the fourth Elf (with 24000 Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with 10000 Calories).
-    // The sum of the Calories carried by these three elves is 45000.
-    //
-    // Find the top three Elves carrying the most Calories. How many Calories are those Elves carrying in total?
-    //
-    // Your puzzle answer was 193697.
-    puzzle_1();
-    puzzle_2();
+impl Solution for Day1 {
+    type Model = Vec<Elve>;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Elve>> {
+        parse_input(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<AocError> {
+        validate(input)
+    }
+
+    fn part1(&self, elves: &Vec<Elve>) -> Result<String> {
+        Ok(puzzle_1(elves)?.to_string())
+    }
+
+    fn part2(&self, elves: &Vec<Elve>) -> Result<String> {
+        Ok(puzzle_2(elves, self.top_n)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::normalize;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_input_splits_elves_on_blank_lines() {
+        let elves = parse_input(EXAMPLE).unwrap();
+        let calories: Vec<u64> = elves
+            .iter()
+            .map(|elve| elve.get_calories().unwrap())
+            .collect();
+        assert_eq!(calories, vec![6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(elves[0].calories_entries, vec![1000, 2000, 3000]);
+        assert_eq!(elves[4].index, 5);
+    }
+
+    #[test]
+    fn puzzle_1_example() {
+        assert_eq!(puzzle_1(&parse_input(EXAMPLE).unwrap()).unwrap(), 24000);
+    }
+
+    #[test]
+    fn parse_input_reports_invalid_calories() {
+        let err = parse_input("1000\n\n20x0\n").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                AocError::Parse {
+                    line: 3,
+                    column: 1,
+                    ..
+                }
+            ),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_input_rejects_negative_calories() {
+        let err = parse_input("1000\n-5\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: negative calories `-5`");
+    }
+
+    #[test]
+    fn totals_past_u32_are_exact_and_overflow_is_an_error() {
+        let big = u32::MAX as u64;
+        let elves = parse_input(&format!("{}\n{}\n\n1\n", big, big)).unwrap();
+        assert_eq!(puzzle_1(&elves).unwrap(), 2 * big);
+        assert_eq!(puzzle_2(&elves, 2).unwrap(), 2 * big + 1);
+
+        let elves = parse_input(&format!("{}\n1\n", u64::MAX)).unwrap();
+        assert!(matches!(puzzle_1(&elves), Err(AocError::Overflow(_))));
+    }
+
+    #[test]
+    fn validate_reports_every_bad_line() {
+        assert!(validate(EXAMPLE).is_empty());
+        let input = format!("1\n-5\nx\n\n{}\n1\n1\n", u64::MAX);
+        let violations: Vec<String> = validate(&input).iter().map(|v| v.to_string()).collect();
+        assert_eq!(violations.len(), 3, "{:?}", violations);
+        assert!(violations[0].starts_with("line 2, column 1: negative calories"));
+        assert!(violations[1].starts_with("line 3, column 1: invalid calories `x`"));
+        assert!(violations[2].starts_with("line 5, column 1: the calories of this elf"));
+        assert!(matches!(validate("\n")[..], [AocError::InvalidData(_)]));
+    }
+
+    #[test]
+    fn empty_input_has_no_elves() {
+        for input in ["", "\n", " \n\n"] {
+            let normalized = normalize(input);
+            let elves = parse_input(normalized.text()).unwrap();
+            assert!(elves.is_empty(), "{:?}", input);
+            for err in [puzzle_1(&elves), puzzle_2(&elves, 3)] {
+                assert_eq!(
+                    err.unwrap_err().to_string(),
+                    "invalid puzzle data: there are no elves"
+                );
+            }
+            assert_eq!(
+                validate(normalized.text())[0].to_string(),
+                "invalid puzzle data: there are no elves"
+            );
+        }
+    }
+
+    #[test]
+    fn puzzle_2_example() {
+        assert_eq!(puzzle_2(&parse_input(EXAMPLE).unwrap(), 3).unwrap(), 45000);
+        assert_eq!(puzzle_2(&parse_input(EXAMPLE).unwrap(), 5).unwrap(), 55000);
+    }
+
+    #[test]
+    fn puzzle_2_needs_three_elves() {
+        let elves = parse_input("1\n\n2\n").unwrap();
+        assert!(matches!(puzzle_2(&elves, 3), Err(AocError::InvalidData(_))));
+    }
 }
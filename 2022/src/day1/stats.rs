@@ -0,0 +1,295 @@
+use crate::{
+    day1::{no_elves, Elve},
+    error::{checked_sum, AocError, Result},
+};
+
+/// The percentiles reported alongside the mean and median.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Percentile {
+    pub percentile: u8,
+    pub calories: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElveSummary {
+    pub index: usize,
+    pub items: usize,
+    pub calories: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaviestItem {
+    pub elve_index: usize,
+    pub calories: u64,
+}
+
+/// Elves whose totals fall in `start..=end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalorieStats {
+    pub elves: Vec<ElveSummary>,
+    pub items: usize,
+    pub total: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: u64,
+    pub max: u64,
+    pub percentiles: Vec<Percentile>,
+    pub heaviest_item: Option<HeaviestItem>,
+    pub histogram: Vec<Bucket>,
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[u64], percentile: u8) -> u64 {
+    let rank = (percentile as usize * sorted.len()).div_ceil(100);
+    sorted[rank.max(1) - 1]
+}
+
+fn median(sorted: &[u64]) -> f64 {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.0
+    } else {
+        sorted[middle] as f64
+    }
+}
+
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    // Work in u128 so the span of totals near u64::MAX cannot wrap.
+    let (min, max) = (sorted[0] as u128, sorted[sorted.len() - 1] as u128);
+    let width = (max - min + 1).div_ceil(buckets as u128);
+    let mut histogram: Vec<Bucket> = (0..buckets as u128)
+        .map(|bucket| min + bucket * width)
+        .take_while(|start| *start <= max)
+        .map(|start| Bucket {
+            start: start as u64,
+            end: (start + width - 1).min(max) as u64,
+            count: 0,
+        })
+        .collect();
+    for &calories in sorted {
+        histogram[((calories as u128 - min) / width) as usize].count += 1;
+    }
+    histogram
+}
+
+pub fn calorie_stats(elves: &[Elve], buckets: usize) -> Result<CalorieStats> {
+    if elves.is_empty() {
+        return Err(no_elves());
+    }
+    if buckets == 0 {
+        return Err(AocError::invalid_data(
+            "the histogram needs at least one bucket",
+        ));
+    }
+
+    let summaries = elves
+        .iter()
+        .map(|elve| {
+            Ok(ElveSummary {
+                index: elve.index,
+                items: elve.calories_entries.len(),
+                calories: elve.get_calories()?,
+            })
+        })
+        .collect::<Result<Vec<ElveSummary>>>()?;
+    let mut sorted: Vec<u64> = summaries.iter().map(|elve| elve.calories).collect();
+    sorted.sort_unstable();
+
+    let total = checked_sum("the calories of all elves", sorted.iter().copied())?;
+    let heaviest_item = elves
+        .iter()
+        .flat_map(|elve| {
+            elve.calories_entries.iter().map(|&calories| HeaviestItem {
+                elve_index: elve.index,
+                calories,
+            })
+        })
+        .reduce(|heaviest, item| {
+            if item.calories > heaviest.calories {
+                item
+            } else {
+                heaviest
+            }
+        });
+
+    Ok(CalorieStats {
+        items: summaries.iter().map(|elve| elve.items).sum(),
+        total,
+        mean: total as f64 / sorted.len() as f64,
+        median: median(&sorted),
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+        percentiles: PERCENTILES
+            .iter()
+            .map(|&p| Percentile {
+                percentile: p,
+                calories: percentile(&sorted, p),
+            })
+            .collect(),
+        heaviest_item,
+        histogram: histogram(&sorted, buckets),
+        elves: summaries,
+    })
+}
+
+impl CalorieStats {
+    pub fn print_table(&self) {
+        let items_per_elve = self.elves.iter().map(|elve| elve.items);
+        println!("Elves           {:>12}", self.elves.len());
+        println!("Items           {:>12}", self.items);
+        println!(
+            "Items per elf   {:>12}",
+            format!(
+                "{}..={}",
+                items_per_elve.clone().min().unwrap_or_default(),
+                items_per_elve.max().unwrap_or_default()
+            )
+        );
+        println!("Total calories  {:>12}", self.total);
+        println!("Mean            {:>12.1}", self.mean);
+        println!("Median          {:>12.1}", self.median);
+        println!("Min             {:>12}", self.min);
+        for percentile in &self.percentiles {
+            println!("p{:<14} {:>12}", percentile.percentile, percentile.calories);
+        }
+        println!("Max             {:>12}", self.max);
+        if let Some(item) = &self.heaviest_item {
+            println!(
+                "Heaviest item   {:>12}  (elf {})",
+                item.calories, item.elve_index
+            );
+        }
+
+        println!();
+        let largest = self
+            .histogram
+            .iter()
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        for bucket in &self.histogram {
+            println!(
+                "{:>8} ..= {:>8}  {:>5}  {}",
+                bucket.start,
+                bucket.end,
+                bucket.count,
+                "#".repeat(bucket.count * 40 / largest)
+            );
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let percentiles: Vec<String> = self
+            .percentiles
+            .iter()
+            .map(|p| format!("\"p{}\": {}", p.percentile, p.calories))
+            .collect();
+        let heaviest_item = match &self.heaviest_item {
+            Some(item) => format!(
+                "{{\"elf\": {}, \"calories\": {}}}",
+                item.elve_index, item.calories
+            ),
+            None => "null".to_string(),
+        };
+        let buckets: Vec<String> = self
+            .histogram
+            .iter()
+            .map(|bucket| {
+                format!(
+                    "    {{\"start\": {}, \"end\": {}, \"count\": {}}}",
+                    bucket.start, bucket.end, bucket.count
+                )
+            })
+            .collect();
+        let elves: Vec<String> = self
+            .elves
+            .iter()
+            .map(|elve| {
+                format!(
+                    "    {{\"elf\": {}, \"items\": {}, \"calories\": {}}}",
+                    elve.index, elve.items, elve.calories
+                )
+            })
+            .collect();
+
+        let fields = [
+            format!("\"elves\": {}", self.elves.len()),
+            format!("\"items\": {}", self.items),
+            format!("\"total\": {}", self.total),
+            format!("\"mean\": {}", self.mean),
+            format!("\"median\": {}", self.median),
+            format!("\"min\": {}", self.min),
+            format!("\"max\": {}", self.max),
+            format!("\"percentiles\": {{{}}}", percentiles.join(", ")),
+            format!("\"heaviest_item\": {}", heaviest_item),
+            format!("\"histogram\": [\n{}\n  ]", buckets.join(",\n")),
+            format!("\"per_elf\": [\n{}\n  ]", elves.join(",\n")),
+        ];
+        format!("{{\n  {}\n}}\n", fields.join(",\n  "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::parse_input;
+
+    #[test]
+    fn calorie_stats_example() {
+        let elves = parse_input(include_str!("example.txt")).unwrap();
+        let stats = calorie_stats(&elves, 3).unwrap();
+        assert_eq!(stats.items, 10);
+        assert_eq!(stats.total, 55000);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!((stats.min, stats.max), (4000, 24000));
+        assert_eq!(stats.percentiles[0].calories, 4000);
+        assert_eq!(stats.percentiles[3].calories, 24000);
+        assert_eq!(
+            stats.heaviest_item,
+            Some(HeaviestItem {
+                elve_index: 5,
+                calories: 10000
+            })
+        );
+        let counts: Vec<usize> = stats.histogram.iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, vec![3, 1, 1]);
+        assert_eq!(stats.histogram[2].end, 24000);
+    }
+
+    #[test]
+    fn calorie_stats_reports_overflowing_total() {
+        let elves = parse_input(&format!("{}\n\n1\n", u64::MAX)).unwrap();
+        assert!(matches!(
+            calorie_stats(&elves, 2),
+            Err(AocError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn median_averages_middle_totals() {
+        assert_eq!(median(&[1, 2, 3, 10]), 2.5);
+    }
+
+    #[test]
+    fn histogram_of_equal_totals_has_one_bucket() {
+        assert_eq!(
+            histogram(&[7, 7], 4),
+            vec![Bucket {
+                start: 7,
+                end: 7,
+                count: 2
+            }]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::{io::BufRead, path::Path};
+
+use crate::{
+    day1::{no_elves, parse_calories, sum_top_n, RankedElve, TopN},
+    error::{AocError, Result},
+    normalize::{LineNormalizer, Normalization},
+};
+
+/// What a single pass over an inventory keeps: counts and the best `n`
+/// elves, so memory stays bounded whatever the size of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamSummary {
+    pub elves: usize,
+    pub items: usize,
+    pub top: Vec<RankedElve>,
+    /// The clean-ups applied to the lines while reading them.
+    pub normalizations: Vec<Normalization>,
+    n: usize,
+}
+
+impl StreamSummary {
+    pub fn part1(&self) -> Result<u64> {
+        self.top
+            .first()
+            .map(|elve| elve.calories)
+            .ok_or_else(no_elves)
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        sum_top_n(&self.top, self.n, self.elves)
+    }
+}
+
+/// Reads an inventory line by line, reusing one line buffer and keeping
+/// only the running total of the current elf. Lines go through the same
+/// clean-up as [`crate::normalize::normalize`]. `source` names the reader in
+/// I/O errors.
+pub fn parse_stream(mut reader: impl BufRead, source: &Path, n: usize) -> Result<StreamSummary> {
+    let mut top = TopN::new(n.max(1));
+    let mut elves = 0;
+    let mut items = 0;
+    let mut index = 1;
+    let mut current_calories = 0u64;
+    let mut normalizer = LineNormalizer::new();
+
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(|err| AocError::Io {
+            path: source.to_path_buf(),
+            source: err,
+        })?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let Some(content) = normalizer.push(&line) else {
+            continue;
+        };
+        if content.after_separator {
+            top.push(index, current_calories);
+            elves += 1;
+            index += 1;
+            current_calories = 0;
+        }
+        current_calories = parse_calories(line_number, content.text)?
+            .checked_add(current_calories)
+            .ok_or_else(|| AocError::overflow(format!("the calories carried by elf {}", index)))?;
+        items += 1;
+    }
+    if items == 0 {
+        return Err(no_elves());
+    }
+    top.push(index, current_calories);
+    elves += 1;
+
+    Ok(StreamSummary {
+        elves,
+        items,
+        top: top.into_ranked(),
+        normalizations: normalizer.applied(),
+        n,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day1::{parse_input, puzzle_1, puzzle_2},
+        normalize::normalize,
+    };
+
+    fn stream(input: &str, n: usize) -> Result<StreamSummary> {
+        parse_stream(input.as_bytes(), Path::new("<test>"), n)
+    }
+
+    #[test]
+    fn parse_stream_matches_in_memory_answers() {
+        for input in [
+            include_str!("example.txt"),
+            include_str!("input.txt"),
+            "1\r\n2\r\n\r\n4\r\n\r\n3\r\n",
+            "\u{feff}5 \n\n\n6\n\n7\r\n\n",
+        ] {
+            let normalized = normalize(input);
+            let elves = parse_input(normalized.text()).unwrap();
+            let summary = stream(input, 3).unwrap();
+            assert_eq!(summary.elves, elves.len());
+            assert_eq!(summary.part1().unwrap(), puzzle_1(&elves).unwrap());
+            assert_eq!(summary.part2().unwrap(), puzzle_2(&elves, 3).unwrap());
+            assert_eq!(summary.normalizations, normalized.applied());
+        }
+    }
+
+    #[test]
+    fn parse_stream_counts_items() {
+        let summary = stream(include_str!("example.txt"), 1).unwrap();
+        assert_eq!((summary.elves, summary.items), (5, 10));
+        assert_eq!(
+            summary.top,
+            vec![RankedElve {
+                index: 4,
+                calories: 24000
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_stream_reports_line_of_invalid_calories() {
+        let err = stream("1\n\nx\n", 3).unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 3, .. }), "{:?}", err);
+    }
+
+    #[test]
+    fn parse_stream_rejects_an_empty_inventory() {
+        for input in ["", "\n\n", "\u{feff} \r\n"] {
+            let err = stream(input, 3).unwrap_err();
+            assert_eq!(err.to_string(), "invalid puzzle data: there are no elves");
+        }
+    }
+
+    #[test]
+    fn parse_stream_reports_overflowing_elf() {
+        let err = stream(&format!("1\n\n{}\n1\n", u64::MAX), 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the calories carried by elf 2 does not fit in 64 bits"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{day1::Elve, error::Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RankedElve {
+    pub index: usize,
+    pub calories: u64,
+}
+
+/// Keeps the `n` elves with the most calories seen so far, in a min-heap
+/// bounded to `n` entries. Ties go to the elf seen first.
+#[derive(Debug)]
+pub struct TopN {
+    n: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl TopN {
+    pub fn new(n: usize) -> TopN {
+        TopN {
+            n,
+            heap: BinaryHeap::with_capacity(n + 1),
+        }
+    }
+
+    pub fn push(&mut self, index: usize, calories: u64) {
+        if self.n == 0 {
+            return;
+        }
+        self.heap.push(Reverse((calories, Reverse(index))));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    /// The kept elves, most calories first.
+    pub fn into_ranked(self) -> Vec<RankedElve> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(index)))| RankedElve { index, calories })
+            .collect()
+    }
+}
+
+pub fn top_n(elves: &[Elve], n: usize) -> Result<Vec<RankedElve>> {
+    let mut top = TopN::new(n);
+    for elve in elves {
+        top.push(elve.index, elve.get_calories()?);
+    }
+    Ok(top.into_ranked())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::parse_input;
+
+    #[test]
+    fn top_n_ranks_example_elves() {
+        let elves = parse_input(include_str!("example.txt")).unwrap();
+        let ranked = top_n(&elves, 3).unwrap();
+        let expected = [(4, 24000), (3, 11000), (5, 10000)]
+            .map(|(index, calories)| RankedElve { index, calories });
+        assert_eq!(ranked, expected);
+    }
+
+    #[test]
+    fn top_n_keeps_first_elf_on_ties() {
+        let mut top = TopN::new(2);
+        for (index, calories) in [(1, 5), (2, 7), (3, 5), (4, 1)] {
+            top.push(index, calories);
+        }
+        let indices: Vec<usize> = top.into_ranked().iter().map(|elve| elve.index).collect();
+        assert_eq!(indices, vec![2, 1]);
+    }
+
+    #[test]
+    fn top_n_returns_every_elf_when_n_is_larger() {
+        let elves = parse_input("1\n\n2\n").unwrap();
+        assert_eq!(top_n(&elves, 5).unwrap().len(), 2);
+        assert!(top_n(&elves, 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    day2::{
+        rules::Rules,
+        utils::{calculate_score, Round, RoundResult},
+    },
+    error::{AocError, Result},
+};
+
+/// How well a way of answering the opponent column does over a guide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: &'static str,
+    /// The total score, or its expected value for a random strategy.
+    pub score: f64,
+    /// The share of rounds won, between 0 and 1.
+    pub win_rate: f64,
+}
+
+/// Guesses the opponent's next shape from the ones already played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Predictor {
+    /// How often each shape was played so far.
+    Frequency,
+    /// How often each shape followed the opponent's previous one.
+    Markov,
+}
+
+/// Scores playing `mine` round by round against `opponent`.
+fn report(
+    rules: &Rules,
+    strategy: &'static str,
+    opponent: &[usize],
+    mine: Vec<usize>,
+) -> Result<Report> {
+    let rounds: Vec<Round> = opponent
+        .iter()
+        .zip(mine)
+        .map(|(&opponent_shape, my_shape)| Round::new(opponent_shape, my_shape))
+        .collect();
+    let wins = rounds
+        .iter()
+        .filter(|round| rules.result(round.opponent_shape, round.my_shape) == RoundResult::Win)
+        .count();
+    Ok(Report {
+        strategy,
+        score: calculate_score(rules, &rounds)? as f64,
+        win_rate: wins as f64 / rounds.len().max(1) as f64,
+    })
+}
+
+/// The shape with the highest expected score against an opponent who plays
+/// each shape in proportion to `weights`. Ties go to the first shape.
+fn best_response(rules: &Rules, weights: &[u64]) -> Result<usize> {
+    let expected = |mine: usize| -> Result<u64> {
+        let mut total: u64 = 0;
+        for (opponent, weight) in weights.iter().enumerate() {
+            total = weight
+                .checked_mul(rules.score(opponent, mine)?)
+                .and_then(|points| total.checked_add(points))
+                .ok_or_else(|| AocError::overflow("the expected score of a reply"))?;
+        }
+        Ok(total)
+    };
+    let mut best = (0, expected(0)?);
+    for mine in 1..rules.len() {
+        let score = expected(mine)?;
+        if score > best.1 {
+            best = (mine, score);
+        }
+    }
+    Ok(best.0)
+}
+
+/// The best possible score, knowing every move of the opponent in advance.
+pub fn optimal(rules: &Rules, opponent: &[usize]) -> Result<Report> {
+    let mine = opponent
+        .iter()
+        .map(|&shape| {
+            let mut weights = vec![0; rules.len()];
+            weights[shape] = 1;
+            best_response(rules, &weights)
+        })
+        .collect::<Result<Vec<usize>>>()?;
+    report(rules, "optimal", opponent, mine)
+}
+
+/// The expected score of picking a shape uniformly at random each round,
+/// which is the mean over always playing the same shape.
+pub fn uniform_random(rules: &Rules, opponent: &[usize]) -> Result<Report> {
+    let constant = (0..rules.len())
+        .map(|shape| {
+            report(
+                rules,
+                "uniform random",
+                opponent,
+                vec![shape; opponent.len()],
+            )
+        })
+        .collect::<Result<Vec<Report>>>()?;
+    let shapes = constant.len() as f64;
+    Ok(Report {
+        strategy: "uniform random",
+        score: constant.iter().map(|report| report.score).sum::<f64>() / shapes,
+        win_rate: constant.iter().map(|report| report.win_rate).sum::<f64>() / shapes,
+    })
+}
+
+/// Plays the best response to `predictor`'s guess each round, learning only
+/// from the rounds already played. Counts start at one so that an unseen
+/// shape is never ruled out.
+pub fn predict(rules: &Rules, opponent: &[usize], predictor: Predictor) -> Result<Report> {
+    let mut frequencies = vec![1; rules.len()];
+    let mut transitions = vec![vec![1; rules.len()]; rules.len()];
+    let uniform = vec![1; rules.len()];
+    let mut previous: Option<usize> = None;
+    let mut mine = Vec::with_capacity(opponent.len());
+    for &shape in opponent {
+        let weights = match (predictor, previous) {
+            (Predictor::Frequency, _) => &frequencies,
+            (Predictor::Markov, Some(previous)) => &transitions[previous],
+            (Predictor::Markov, None) => &uniform,
+        };
+        mine.push(best_response(rules, weights)?);
+
+        frequencies[shape] += 1;
+        if let Some(previous) = previous {
+            transitions[previous][shape] += 1;
+        }
+        previous = Some(shape);
+    }
+    let strategy = match predictor {
+        Predictor::Frequency => "frequency",
+        Predictor::Markov => "markov",
+    };
+    report(rules, strategy, opponent, mine)
+}
+
+/// Every analysis of `opponent`, from the best case down to the predictors.
+pub fn analyse(rules: &Rules, opponent: &[usize]) -> Result<Vec<Report>> {
+    Ok(vec![
+        optimal(rules, opponent)?,
+        uniform_random(rules, opponent)?,
+        predict(rules, opponent, Predictor::Frequency)?,
+        predict(rules, opponent, Predictor::Markov)?,
+    ])
+}
+
+pub fn print_table(reports: &[Report]) {
+    println!("{:<16}  {:>10}  {:>8}", "Strategy", "Score", "Win rate");
+    for report in reports {
+        println!(
+            "{:<16}  {:>10.1}  {:>7.1}%",
+            report.strategy,
+            report.score,
+            report.win_rate * 100.0
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rock, Paper and Scissors.
+    const OPPONENT: [usize; 3] = [0, 1, 2];
+
+    #[test]
+    fn optimal_always_wins() {
+        let report = optimal(&Rules::rock_paper_scissors(), &OPPONENT).unwrap();
+        // Paper, Scissors and Rock: 8 + 9 + 7.
+        assert_eq!((report.score, report.win_rate), (24.0, 1.0));
+    }
+
+    #[test]
+    fn uniform_random_wins_a_third() {
+        let report = uniform_random(&Rules::rock_paper_scissors(), &OPPONENT).unwrap();
+        assert_eq!(report.score, 15.0);
+        assert!((report.win_rate - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn predictors_exploit_a_repetitive_opponent() {
+        let rules = Rules::rock_paper_scissors();
+        let opponent = vec![0; 10];
+        let frequency = predict(&rules, &opponent, Predictor::Frequency).unwrap();
+        // The first guess is blind; every later one plays Paper.
+        assert_eq!(frequency.win_rate, 0.9);
+
+        let cycle: Vec<usize> = OPPONENT.iter().cycle().take(300).copied().collect();
+        let markov = predict(&rules, &cycle, Predictor::Markov).unwrap();
+        let frequency = predict(&rules, &cycle, Predictor::Frequency).unwrap();
+        assert!(markov.win_rate > 0.9, "{:?}", markov);
+        assert!(markov.score > frequency.score);
+    }
+
+    #[test]
+    fn optimal_wins_every_round_of_larger_games() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let opponent: Vec<usize> = (0..rules.len()).collect();
+        let report = optimal(&rules, &opponent).unwrap();
+        assert_eq!(report.win_rate, 1.0);
+        let random = uniform_random(&rules, &opponent).unwrap();
+        assert!((random.win_rate - 2.0 / 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expected_scores_overflow_instead_of_wrapping() {
+        let rules = Rules::rock_paper_scissors();
+        let err = best_response(&rules, &[u64::MAX, 1, 1]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the expected score of a reply does not fit in 64 bits"
+        );
+        // Every round score fits, but not their sum.
+        let rules =
+            Rules::parse("Rock 18446744073709551609\nPaper 2\nScissors 3\ncyclic\n").unwrap();
+        assert!(best_response(&rules, &[1, 1, 1]).is_err());
+        assert_eq!(best_response(&rules, &[1, 0, 0]).unwrap(), 0);
+    }
+}
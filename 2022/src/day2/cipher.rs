@@ -0,0 +1,817 @@
+use std::{fmt, fs, iter::Peekable, path::Path, str::Chars};
+
+use crate::{
+    day2::{
+        rules::{alternatives, Rules},
+        utils::{GuideEntry, Round, RoundResult, Symbol},
+    },
+    error::{AocError, Result},
+    solution::Part,
+};
+
+/// What a symbol of the strategy guide stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Meaning {
+    /// A shape of the game, by name.
+    Shape(String),
+    Result(RoundResult),
+}
+
+impl Meaning {
+    /// Reads `Win`, `Draw` or `Loss` in any case, and anything else as the
+    /// name of a shape, which only the [`Rules`] of the game can confirm.
+    pub fn parse(name: &str) -> Meaning {
+        match name.to_ascii_lowercase().as_str() {
+            "win" => Meaning::Result(RoundResult::Win),
+            "draw" => Meaning::Result(RoundResult::Draw),
+            "loss" => Meaning::Result(RoundResult::Loss),
+            _ => Meaning::Shape(name.to_string()),
+        }
+    }
+
+    fn shape(name: &str) -> Meaning {
+        Meaning::Shape(name.to_string())
+    }
+}
+
+impl fmt::Display for Meaning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Meaning::Shape(name) => name,
+            Meaning::Result(RoundResult::Win) => "Win",
+            Meaning::Result(RoundResult::Draw) => "Draw",
+            Meaning::Result(RoundResult::Loss) => "Loss",
+        };
+        f.pad(name)
+    }
+}
+
+/// A second-column meaning with its shape looked up in the rules.
+#[derive(Debug, Clone, Copy)]
+enum Resolved<'a> {
+    Shape(usize),
+    Result(&'a RoundResult),
+}
+
+/// Maps the symbols of both columns of a strategy guide to what they mean:
+/// a shape for the opponent, and a shape or a result for the second column.
+/// Shapes are named, and only looked up in the [`Rules`] of a game when
+/// decoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cipher {
+    opponent: Vec<(String, String)>,
+    second: Vec<(String, Meaning)>,
+}
+
+impl Cipher {
+    pub fn new(opponent: Vec<(String, String)>, second: Vec<(String, Meaning)>) -> Result<Cipher> {
+        check_symbols("opponent", &opponent)?;
+        check_symbols("second", &second)?;
+        Ok(Cipher { opponent, second })
+    }
+
+    /// The part 1 reading: `X`, `Y` and `Z` are the first three shapes of
+    /// `rules`, to play.
+    pub fn shapes(rules: &Rules) -> Cipher {
+        let second = ["X", "Y", "Z"]
+            .into_iter()
+            .zip(rules.names())
+            .map(|(symbol, name)| (symbol.to_string(), Meaning::shape(name)))
+            .collect();
+        Cipher::new(standard_opponent(rules), second).expect("the part 1 cipher is valid")
+    }
+
+    /// The part 2 reading: `X`, `Y` and `Z` are how the round must end.
+    pub fn outcomes(rules: &Rules) -> Cipher {
+        Cipher::new(
+            standard_opponent(rules),
+            table(&[
+                ("X", Meaning::Result(RoundResult::Loss)),
+                ("Y", Meaning::Result(RoundResult::Draw)),
+                ("Z", Meaning::Result(RoundResult::Win)),
+            ]),
+        )
+        .expect("the part 2 cipher is valid")
+    }
+
+    pub fn opponent(&self) -> &[(String, String)] {
+        &self.opponent
+    }
+
+    pub fn second(&self) -> &[(String, Meaning)] {
+        &self.second
+    }
+
+    /// Replaces the columns that `cipher_override` redefines for `part`.
+    pub fn with_override(mut self, cipher_override: &CipherOverride, part: Part) -> Cipher {
+        if let Some(opponent) = &cipher_override.opponent {
+            self.opponent = opponent.clone();
+        }
+        if let Some(second) = cipher_override.second(part) {
+            self.second = second.clone();
+        }
+        self
+    }
+
+    /// Checks that every shape the cipher names is one of `rules`.
+    pub fn check_shapes(&self, rules: &Rules) -> Result<()> {
+        self.resolve(rules).map(|_| ())
+    }
+
+    /// Looks up the shapes of both columns, in the order of the columns.
+    fn resolve(&self, rules: &Rules) -> Result<(Vec<usize>, Vec<Resolved<'_>>)> {
+        let opponent = self
+            .opponent
+            .iter()
+            .map(|(symbol, name)| find_shape(rules, symbol, name))
+            .collect::<Result<Vec<usize>>>()?;
+        let second = self
+            .second
+            .iter()
+            .map(|(symbol, meaning)| match meaning {
+                Meaning::Shape(name) => find_shape(rules, symbol, name).map(Resolved::Shape),
+                Meaning::Result(result) => Ok(Resolved::Result(result)),
+            })
+            .collect::<Result<Vec<Resolved>>>()?;
+        Ok((opponent, second))
+    }
+
+    /// The rounds of `guide` as games of `rules`, choosing my shape with
+    /// [`Rules::choose`] when the second column gives a result.
+    pub fn decode(&self, rules: &Rules, guide: &[GuideEntry]) -> Result<Vec<Round>> {
+        let (opponent, second) = self.resolve(rules)?;
+        guide
+            .iter()
+            .map(|entry| {
+                let opponent_shape = position(&self.opponent, &entry.opponent)
+                    .map(|index| opponent[index])
+                    .ok_or_else(|| {
+                        unknown_symbol(entry.line, &entry.opponent, "shape", &self.opponent)
+                    })?;
+                let my_shape =
+                    match position(&self.second, &entry.second).map(|index| second[index]) {
+                        Some(Resolved::Shape(my_shape)) => my_shape,
+                        Some(Resolved::Result(result)) => {
+                            rules.choose(opponent_shape, result).ok_or_else(|| {
+                                AocError::parse(
+                                    entry.line,
+                                    entry.second.column,
+                                    format!(
+                                        "no shape gets a {} against {}",
+                                        rules.name(opponent_shape),
+                                        Meaning::Result(result.clone())
+                                    ),
+                                )
+                            })?
+                        }
+                        None => {
+                            return Err(unknown_symbol(
+                                entry.line,
+                                &entry.second,
+                                "second column",
+                                &self.second,
+                            ))
+                        }
+                    };
+                Ok(Round::new(opponent_shape, my_shape))
+            })
+            .collect()
+    }
+
+    /// Every symbol of `entry` the cipher does not know, in column order.
+    pub fn check(&self, entry: &GuideEntry) -> Vec<AocError> {
+        let mut unknown = Vec::new();
+        if position(&self.opponent, &entry.opponent).is_none() {
+            unknown.push(unknown_symbol(
+                entry.line,
+                &entry.opponent,
+                "shape",
+                &self.opponent,
+            ));
+        }
+        if position(&self.second, &entry.second).is_none() {
+            unknown.push(unknown_symbol(
+                entry.line,
+                &entry.second,
+                "second column",
+                &self.second,
+            ));
+        }
+        unknown
+    }
+}
+
+/// Columns that replace those of the built-in ciphers, read from a cipher
+/// file or from `--opponent`/`--second` on the command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CipherOverride {
+    pub opponent: Option<Vec<(String, String)>>,
+    /// The second column of both parts.
+    pub second: Option<Vec<(String, Meaning)>>,
+    /// The second column of one part, which wins over `second`.
+    pub part1_second: Option<Vec<(String, Meaning)>>,
+    pub part2_second: Option<Vec<(String, Meaning)>>,
+}
+
+impl CipherOverride {
+    pub fn load(path: &Path) -> Result<CipherOverride> {
+        let content = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        CipherOverride::parse(&content)
+    }
+
+    /// Reads a cipher file with an `opponent` and/or a `second` table, as
+    /// JSON when it starts with `{` and as TOML otherwise. A `part1.second`
+    /// or `part2.second` table sets the second column of one part only:
+    ///
+    /// ```toml
+    /// [opponent]
+    /// A = "Rock"
+    /// B = "Paper"
+    /// C = "Scissors"
+    ///
+    /// [part1.second]
+    /// X = "Rock"
+    /// Y = "Paper"
+    /// Z = "Scissors"
+    ///
+    /// [part2.second]
+    /// X = "Loss"
+    /// Y = "Draw"
+    /// Z = "Win"
+    /// ```
+    pub fn parse(content: &str) -> Result<CipherOverride> {
+        let sections = if content.trim_start().starts_with('{') {
+            parse_json(content)?
+        } else {
+            parse_toml(content)?
+        };
+        let mut cipher_override = CipherOverride::default();
+        for section in sections {
+            cipher_override.set(&section.name, &section.entries)?;
+        }
+        Ok(cipher_override)
+    }
+
+    /// Reads one column from `SYMBOL=MEANING` pairs separated by commas.
+    pub fn set_column(&mut self, column: &str, mappings: &str) -> Result<()> {
+        let entries = mappings
+            .split(',')
+            .enumerate()
+            .map(|(index, mapping)| match mapping.split_once('=') {
+                Some((symbol, meaning)) => Ok(Entry {
+                    line: 1,
+                    column: index + 1,
+                    symbol: symbol.trim().to_string(),
+                    meaning: meaning.trim().to_string(),
+                }),
+                None => Err(AocError::invalid_data(format!(
+                    "expected `SYMBOL=MEANING`, found `{}`",
+                    mapping
+                ))),
+            })
+            .collect::<Result<Vec<Entry>>>()?;
+        // Positions mean nothing on the command line, so keep the message only.
+        self.set(column, &entries).map_err(|err| match err {
+            AocError::Parse { message, .. } => AocError::invalid_data(message),
+            err => err,
+        })
+    }
+
+    /// Takes the columns `other` defines, keeping the rest.
+    pub fn merge(&mut self, other: CipherOverride) {
+        if other.opponent.is_some() {
+            self.opponent = other.opponent;
+        }
+        if other.second.is_some() {
+            self.second = other.second;
+        }
+        if other.part1_second.is_some() {
+            self.part1_second = other.part1_second;
+        }
+        if other.part2_second.is_some() {
+            self.part2_second = other.part2_second;
+        }
+    }
+
+    /// The second column of `part`: its own if set, else the shared one.
+    pub fn second(&self, part: Part) -> Option<&Vec<(String, Meaning)>> {
+        let own = match part {
+            Part::One => &self.part1_second,
+            Part::Two => &self.part2_second,
+        };
+        own.as_ref().or(self.second.as_ref())
+    }
+
+    /// Checks that every shape the override names is one of `rules`.
+    pub fn check_shapes(&self, rules: &Rules) -> Result<()> {
+        let opponent = self.opponent.iter().flatten();
+        let second = [&self.second, &self.part1_second, &self.part2_second]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|(symbol, meaning)| match meaning {
+                Meaning::Shape(name) => Some((symbol, name)),
+                Meaning::Result(_) => None,
+            });
+        for (symbol, name) in opponent.map(|(symbol, name)| (symbol, name)).chain(second) {
+            find_shape(rules, symbol, name)?;
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, column: &str, entries: &[Entry]) -> Result<()> {
+        let meanings: Vec<(String, Meaning)> = entries
+            .iter()
+            .map(|entry| (entry.symbol.clone(), Meaning::parse(&entry.meaning)))
+            .collect();
+        match column {
+            "opponent" => {
+                let shapes = entries
+                    .iter()
+                    .zip(meanings)
+                    .map(|(entry, (symbol, meaning))| match meaning {
+                        Meaning::Shape(name) => Ok((symbol, name)),
+                        Meaning::Result(_) => Err(entry.error(format!(
+                            "opponent symbol `{}` must stand for a shape",
+                            symbol
+                        ))),
+                    })
+                    .collect::<Result<Vec<(String, String)>>>()?;
+                check_symbols(column, &shapes)?;
+                self.opponent = Some(shapes);
+            }
+            "second" | "part1.second" | "part2.second" => {
+                check_symbols(column, &meanings)?;
+                let second = match column {
+                    "part1.second" => &mut self.part1_second,
+                    "part2.second" => &mut self.part2_second,
+                    _ => &mut self.second,
+                };
+                *second = Some(meanings);
+            }
+            _ => {
+                return Err(AocError::invalid_data(format!(
+                    "unknown column `{}`, expected opponent, second, part1.second or part2.second",
+                    column
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `A`, `B`, `C`... for the shapes of `rules` in order.
+fn standard_opponent(rules: &Rules) -> Vec<(String, String)> {
+    ('A'..='Z')
+        .zip(rules.names())
+        .map(|(symbol, name)| (symbol.to_string(), name.to_string()))
+        .collect()
+}
+
+fn table<T: Clone>(entries: &[(&str, T)]) -> Vec<(String, T)> {
+    entries
+        .iter()
+        .map(|(symbol, meaning)| (symbol.to_string(), meaning.clone()))
+        .collect()
+}
+
+fn position<T>(table: &[(String, T)], symbol: &Symbol) -> Option<usize> {
+    table.iter().position(|(known, _)| *known == symbol.text)
+}
+
+/// The shape of `rules` called `name`, which `symbol` stands for.
+fn find_shape(rules: &Rules, symbol: &str, name: &str) -> Result<usize> {
+    rules.find(name).ok_or_else(|| {
+        AocError::invalid_data(format!(
+            "`{}` stands for `{}`, which is not a shape of the game, expected {}",
+            symbol,
+            name,
+            alternatives(&rules.names())
+        ))
+    })
+}
+
+/// Symbols must be non-empty, distinct and free of whitespace, which
+/// separates the columns of a guide.
+fn check_symbols<T>(column: &str, table: &[(String, T)]) -> Result<()> {
+    if table.is_empty() {
+        return Err(AocError::invalid_data(format!(
+            "the {} column needs at least one symbol",
+            column
+        )));
+    }
+    for (index, (symbol, _)) in table.iter().enumerate() {
+        if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+            return Err(AocError::invalid_data(format!(
+                "invalid {} symbol `{}`",
+                column, symbol
+            )));
+        }
+        if table[..index].iter().any(|(other, _)| other == symbol) {
+            return Err(AocError::invalid_data(format!(
+                "{} symbol `{}` is mapped twice",
+                column, symbol
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn unknown_symbol<T>(line: usize, symbol: &Symbol, what: &str, table: &[(String, T)]) -> AocError {
+    let symbols: Vec<&str> = table.iter().map(|(symbol, _)| symbol.as_str()).collect();
+    AocError::parse(
+        line,
+        symbol.column,
+        format!(
+            "unknown {} `{}`, expected {}",
+            what,
+            symbol.text,
+            alternatives(&symbols)
+        ),
+    )
+}
+
+/// A `symbol = meaning` line of a cipher file, with where it starts.
+#[derive(Debug)]
+struct Entry {
+    line: usize,
+    column: usize,
+    symbol: String,
+    meaning: String,
+}
+
+impl Entry {
+    fn error(&self, message: String) -> AocError {
+        AocError::parse(self.line, self.column, message)
+    }
+}
+
+#[derive(Debug)]
+struct Section {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+/// Reads the `[section]` headers and `key = "value"` pairs of TOML, which
+/// is all a cipher needs.
+fn parse_toml(content: &str) -> Result<Vec<Section>> {
+    let mut sections: Vec<Section> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let indent = line.len() - line.trim_start().len() + 1;
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            sections.push(Section {
+                name: name.trim().to_string(),
+                entries: Vec::new(),
+            });
+            continue;
+        }
+        let Some((key, value)) =
+            find_unquoted(line, '=').map(|index| (&line[..index], &line[index + 1..]))
+        else {
+            return Err(AocError::parse(
+                line_number,
+                indent,
+                format!(
+                    "expected `[section]` or `key = \"value\"`, found `{}`",
+                    line
+                ),
+            ));
+        };
+        let key = unquote(key).unwrap_or(key.trim());
+        let Some(value) = unquote(value) else {
+            return Err(AocError::parse(
+                line_number,
+                indent,
+                format!("the value of `{}` must be a quoted string", key),
+            ));
+        };
+        let Some(section) = sections.last_mut() else {
+            return Err(AocError::parse(
+                line_number,
+                indent,
+                format!("`{}` must be inside a `[section]`", key),
+            ));
+        };
+        section.entries.push(Entry {
+            line: line_number,
+            column: indent,
+            symbol: key.to_string(),
+            meaning: value.to_string(),
+        });
+    }
+    Ok(sections)
+}
+
+/// The offset of the first `target` in `line` outside double or single
+/// quotes.
+fn find_unquoted(line: &str, target: char) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match quote {
+            None if c == target => return Some(index),
+            None if c == '"' || c == '\'' => quote = Some(c),
+            Some(open) if c == open => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `line` up to the `#` that starts its comment, if any: one outside quotes.
+fn strip_comment(line: &str) -> &str {
+    find_unquoted(line, '#').map_or(line, |index| &line[..index])
+}
+
+/// The text between the double or single quotes around `text`.
+fn unquote(text: &str) -> Option<&str> {
+    let text = text.trim();
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| text.strip_prefix(quote)?.strip_suffix(quote))
+}
+
+/// Reads a JSON object of objects of strings, the one shape a cipher takes.
+fn parse_json(content: &str) -> Result<Vec<Section>> {
+    let mut json = Json {
+        chars: content.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+    let mut sections = Vec::new();
+    json.object(|json, name| json.section(name, &mut sections))?;
+    json.skip_whitespace();
+    match json.chars.peek().copied() {
+        None => Ok(sections),
+        Some(c) => Err(json.error(format!("unexpected `{}` after the cipher", c))),
+    }
+}
+
+struct Json<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Json<'_> {
+    fn error(&self, message: String) -> AocError {
+        AocError::parse(self.line, self.column, message)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`, found the end", expected))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some(c @ ('"' | '\\' | '/')) => string.push(c),
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    c => {
+                        return Err(
+                            self.error(format!("unsupported escape `\\{}`", c.unwrap_or_default()))
+                        )
+                    }
+                },
+                Some(c) => string.push(c),
+                None => return Err(self.error("unterminated string".to_string())),
+            }
+        }
+    }
+
+    /// Reads the table `name` into `sections`, and any table nested in it,
+    /// like `"part1": {"second": {...}}`, as `part1.second`.
+    fn section(&mut self, name: String, sections: &mut Vec<Section>) -> Result<()> {
+        let (mut entries, mut nested) = (Vec::new(), false);
+        self.object(|json, symbol| {
+            if json.chars.peek() == Some(&'{') {
+                nested = true;
+                return json.section(format!("{}.{}", name, symbol), sections);
+            }
+            let (line, column) = (json.line, json.column);
+            entries.push(Entry {
+                line,
+                column,
+                symbol,
+                meaning: json.string()?,
+            });
+            Ok(())
+        })?;
+        if !nested || !entries.is_empty() {
+            sections.push(Section { name, entries });
+        }
+        Ok(())
+    }
+
+    /// Reads `{ "key": <value>, ... }`, handing each key to `value` to read
+    /// what follows its colon.
+    fn object(&mut self, mut value: impl FnMut(&mut Self, String) -> Result<()>) -> Result<()> {
+        self.expect('{')?;
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&'}') {
+            self.next();
+            return Ok(());
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            self.skip_whitespace();
+            value(self, key)?;
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(()),
+                _ => return Err(self.error("expected `,` or `}`".to_string())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::{
+        utils::{calculate_score, parse_input},
+        Day2,
+    };
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn built_in_ciphers_read_the_example() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = parse_input(EXAMPLE).unwrap();
+        let my_shapes = |cipher: Cipher| -> Vec<&str> {
+            cipher
+                .decode(&rules, &guide)
+                .unwrap()
+                .into_iter()
+                .map(|round| rules.name(round.my_shape))
+                .collect()
+        };
+        assert_eq!(
+            my_shapes(Cipher::shapes(&rules)),
+            vec!["Paper", "Rock", "Scissors"]
+        );
+        assert_eq!(
+            my_shapes(Cipher::outcomes(&rules)),
+            vec!["Rock", "Rock", "Rock"]
+        );
+    }
+
+    #[test]
+    fn decode_reports_unknown_symbols() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = parse_input("A Y\nD X\nA W\n").unwrap();
+        let err = Cipher::shapes(&rules)
+            .decode(&rules, &guide[1..2])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: unknown shape `D`, expected A, B or C"
+        );
+        let err = Cipher::outcomes(&rules)
+            .decode(&rules, &guide[2..])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: unknown second column `W`, expected X, Y or Z"
+        );
+    }
+
+    #[test]
+    fn decode_plays_any_game() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let guide = parse_input("E Z\nD X\nA Y\n").unwrap();
+        let rounds = Cipher::outcomes(&rules).decode(&rules, &guide).unwrap();
+        for (round, result) in
+            rounds
+                .iter()
+                .zip([RoundResult::Win, RoundResult::Loss, RoundResult::Draw])
+        {
+            assert_eq!(rules.result(round.opponent_shape, round.my_shape), result);
+        }
+        // Rock crushes Lizard, is vaporised by Spock and draws with Rock.
+        assert_eq!(calculate_score(&rules, &rounds).unwrap(), 7 + 1 + 4);
+
+        let mut cipher_override = CipherOverride::default();
+        cipher_override.set_column("second", "X=Spock").unwrap();
+        assert!(cipher_override.check_shapes(&rules).is_ok());
+        let err = cipher_override
+            .check_shapes(&Rules::rock_paper_scissors())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid puzzle data: `X` stands for `Spock`, which is not a shape of the game, expected Rock, Paper or Scissors"
+        );
+    }
+
+    #[test]
+    fn toml_and_json_ciphers_agree() {
+        let toml = "# re-encoded guide\n[opponent]\n1 = \"rock\"\n2 = \"paper\"\n3 = \"scissors\"\n\n[second]\nL = 'Loss'\nD = \"Draw\"  # tie\nW = \"Win\"\n";
+        let json = r#"{"opponent": {"1": "rock", "2": "paper", "3": "scissors"},
+            "second": {"L": "Loss", "D": "Draw", "W": "Win"}}"#;
+        let cipher_override = CipherOverride::parse(toml).unwrap();
+        assert_eq!(cipher_override, CipherOverride::parse(json).unwrap());
+
+        let rules = Rules::rock_paper_scissors();
+        let cipher = Cipher::shapes(&rules).with_override(&cipher_override, Part::One);
+        let guide = parse_input("1 D\n2 L\n3 W\n").unwrap();
+        assert_eq!(
+            calculate_score(&rules, &cipher.decode(&rules, &guide).unwrap()).unwrap(),
+            12
+        );
+    }
+
+    #[test]
+    fn part_tables_set_one_part_each() {
+        let toml = "[part1.second]\nX = \"Rock\" # shapes\n[second]\nX = \"Draw\"\n";
+        let json = r#"{"part1": {"second": {"X": "Rock"}}, "second": {"X": "Draw"}}"#;
+        let cipher_override = CipherOverride::parse(toml).unwrap();
+        assert_eq!(cipher_override, CipherOverride::parse(json).unwrap());
+
+        let rules = Rules::rock_paper_scissors();
+        let day2 = Day2::new(rules.clone(), &cipher_override);
+        let guide = parse_input("B X\n").unwrap();
+        let rounds = |cipher: &Cipher| cipher.decode(&rules, &guide).unwrap();
+        assert_eq!(rounds(&day2.part1), vec![Round::new(1, 0)]);
+        assert_eq!(rounds(&day2.part2), vec![Round::new(1, 1)]);
+    }
+
+    #[test]
+    fn toml_comments_and_equals_signs_respect_quotes() {
+        let toml = "[opponent] # the elf\n\"#\" = \"Rock\"#rock\nB = 'Pa#per'\n";
+        let cipher_override = CipherOverride::parse(toml).unwrap();
+        assert_eq!(
+            cipher_override.opponent.unwrap(),
+            vec![
+                ("#".to_string(), "Rock".to_string()),
+                ("B".to_string(), "Pa#per".to_string())
+            ]
+        );
+
+        let cipher_override =
+            CipherOverride::parse("[opponent]\n\"=\" = \"Rock\"\n'a=b' = 'Paper'\n").unwrap();
+        assert_eq!(
+            cipher_override.opponent.unwrap(),
+            vec![
+                ("=".to_string(), "Rock".to_string()),
+                ("a=b".to_string(), "Paper".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn set_column_reads_command_line_mappings() {
+        let rules = Rules::rock_paper_scissors();
+        let mut cipher_override = CipherOverride::default();
+        cipher_override
+            .set_column("second", "X=Rock,Y=Paper,Z=Scissors")
+            .unwrap();
+        let cipher = Cipher::outcomes(&rules).with_override(&cipher_override, Part::Two);
+        assert_eq!(cipher, Cipher::shapes(&rules));
+
+        assert!(cipher_override.set_column("opponent", "A=Win").is_err());
+        assert!(cipher_override
+            .set_column("second", "X=Rock,X=Paper")
+            .is_err());
+        assert!(CipherOverride::parse("[second]\nX = Rock\n").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::{
+    day2::{
+        cipher::{Cipher, Meaning},
+        rules::Rules,
+        utils::{calculate_score, GuideEntry, RoundResult},
+    },
+    error::{AocError, Result},
+};
+
+/// Which reading of the second column [`best`] looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    Max,
+    Min,
+    Target(u64),
+}
+
+/// A reading of the second column and the score the guide gets with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub cipher: Cipher,
+    pub score: u64,
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mappings: Vec<String> = self
+            .cipher
+            .second()
+            .iter()
+            .map(|(symbol, meaning)| format!("{}={}", symbol, meaning))
+            .collect();
+        write!(f, "{}", mappings.join(","))
+    }
+}
+
+/// Every order of `count` distinct `items`, in lexicographic order of their
+/// positions.
+fn arrangements<T: Clone>(items: &[T], count: usize) -> Vec<Vec<T>> {
+    if count == 0 {
+        return vec![Vec::new()];
+    }
+    let mut arrangements = Vec::new();
+    for (index, first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(index);
+        for mut arrangement in self::arrangements(&rest, count - 1) {
+            arrangement.insert(0, first.clone());
+            arrangements.push(arrangement);
+        }
+    }
+    arrangements
+}
+
+/// Scores `guide` as games of `rules` under every way of reading the three
+/// second-column symbols of `base`: first as three distinct shapes in each
+/// order, then as the three results in each order. The opponent column of
+/// `base` is kept.
+pub fn candidates(rules: &Rules, guide: &[GuideEntry], base: &Cipher) -> Result<Vec<Candidate>> {
+    let symbols: Vec<String> = base
+        .second()
+        .iter()
+        .map(|(symbol, _)| symbol.clone())
+        .collect();
+    if symbols.len() != 3 {
+        return Err(AocError::invalid_data(format!(
+            "cracking needs exactly three second-column symbols, found {}",
+            symbols.len()
+        )));
+    }
+
+    let shapes: Vec<Meaning> = rules
+        .names()
+        .into_iter()
+        .map(|name| Meaning::Shape(name.to_string()))
+        .collect();
+    let results = [RoundResult::Loss, RoundResult::Draw, RoundResult::Win].map(Meaning::Result);
+    let mut candidates = Vec::new();
+    for meanings in arrangements(&shapes, 3)
+        .into_iter()
+        .chain(arrangements(&results, 3))
+    {
+        let cipher = Cipher::new(
+            base.opponent().to_vec(),
+            symbols.iter().cloned().zip(meanings).collect(),
+        )?;
+        let score = calculate_score(rules, &cipher.decode(rules, guide)?)?;
+        candidates.push(Candidate { cipher, score });
+    }
+    Ok(candidates)
+}
+
+/// The first of `candidates` that best meets `goal`, if any does.
+pub fn best(candidates: &[Candidate], goal: Goal) -> Option<&Candidate> {
+    match goal {
+        Goal::Max => candidates.iter().reduce(|best, candidate| {
+            if candidate.score > best.score {
+                candidate
+            } else {
+                best
+            }
+        }),
+        Goal::Min => candidates.iter().min_by_key(|candidate| candidate.score),
+        Goal::Target(target) => candidates
+            .iter()
+            .find(|candidate| candidate.score == target),
+    }
+}
+
+pub fn print_table(candidates: &[Candidate], chosen: Option<&Candidate>) {
+    let width = candidates
+        .iter()
+        .map(|candidate| candidate.to_string().len())
+        .chain(["Second column".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "  {:<width$}  {:>8}",
+        "Second column",
+        "Score",
+        width = width
+    );
+    for candidate in candidates {
+        let marker = if chosen == Some(candidate) { "*" } else { " " };
+        println!(
+            "{} {:<width$}  {:>8}",
+            marker,
+            candidate.to_string(),
+            candidate.score,
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::utils::parse_input;
+
+    #[test]
+    fn arrangements_are_in_order() {
+        assert_eq!(arrangements(&[1, 2, 3], 1), vec![vec![1], vec![2], vec![3]]);
+        assert_eq!(
+            arrangements(&[1, 2, 3], 3),
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn crack_example_guide() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = parse_input(include_str!("example.txt")).unwrap();
+        let candidates = candidates(&rules, &guide, &Cipher::shapes(&rules)).unwrap();
+        assert_eq!(candidates.len(), 12);
+        assert_eq!(candidates[0].cipher, Cipher::shapes(&rules));
+        assert_eq!(candidates[0].score, 15);
+        assert!(candidates.contains(&Candidate {
+            cipher: Cipher::outcomes(&rules),
+            score: 12
+        }));
+
+        let max = best(&candidates, Goal::Max).unwrap();
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.score <= max.score));
+        let min = best(&candidates, Goal::Min).unwrap();
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.score >= min.score));
+        assert_eq!(best(&candidates, Goal::Target(12)).unwrap().score, 12);
+        assert_eq!(best(&candidates, Goal::Target(1000)), None);
+    }
+
+    #[test]
+    fn crack_reads_shapes_of_larger_games() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let guide = parse_input(include_str!("example.txt")).unwrap();
+        let candidates = candidates(&rules, &guide, &Cipher::shapes(&rules)).unwrap();
+        // 5 * 4 * 3 readings as shapes, and 6 as results.
+        assert_eq!(candidates.len(), 66);
+    }
+}
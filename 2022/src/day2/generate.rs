@@ -0,0 +1,60 @@
+use std::fmt::Write;
+
+use crate::{generate::Generated, rng::Rng};
+
+/// As many rounds as in the checked-in input.
+pub const DEFAULT_ROUNDS: usize = 2500;
+
+/// A guide of `rounds` rounds with every symbol equally likely. The answers
+/// are worked out with modular arithmetic rather than through the solver's
+/// rules, so that they check it.
+pub fn generate(rng: &mut Rng, rounds: usize) -> Generated {
+    let mut input = String::new();
+    let mut answers = [0, 0];
+    for _ in 0..rounds {
+        let (opponent, second) = (rng.below(3), rng.below(3));
+        writeln!(
+            input,
+            "{} {}",
+            (b'A' + opponent as u8) as char,
+            (b'X' + second as u8) as char
+        )
+        .expect("writing to a string cannot fail");
+
+        // Part 1: the second column is my shape. I win when it is the one
+        // after the opponent's, and draw when it is the same.
+        let result = match (second + 3 - opponent) % 3 {
+            0 => 3,
+            1 => 6,
+            _ => 0,
+        };
+        answers[0] += second + 1 + result;
+        // Part 2: the second column is a loss, draw or win, which the shape
+        // before, equal to or after the opponent's gives.
+        let mine = (opponent + second + 2) % 3;
+        answers[1] += mine + 1 + 3 * second;
+    }
+    Generated { input, answers }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day2::Day2, solution::Solution};
+
+    #[test]
+    fn generated_answers_match_the_solver() {
+        let generated = generate(&mut Rng::new(11), 500);
+        let day2 = Day2::default();
+        let guide = day2.parse(&generated.input).unwrap();
+        assert_eq!(guide.len(), 500);
+        assert_eq!(
+            day2.part1(&guide).unwrap(),
+            generated.answers[0].to_string()
+        );
+        assert_eq!(
+            day2.part2(&guide).unwrap(),
+            generated.answers[1].to_string()
+        );
+    }
+}
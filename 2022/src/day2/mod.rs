@@ -1,65 +1,323 @@
-mod puzzle_1;
-mod puzzle_2;
-mod utils;
-
-use crate::day2::puzzle_1::puzzle_1;
-use crate::day2::puzzle_2::puzzle_2;
-
-pub(crate) fn run() {
-    // --- Day 2: Rock Paper Scissors ---
-    //
-    // The Elves begin to set up camp on the beach. To decide whose tent gets to be closest to the snack storage,
-    // a giant Rock Paper Scissors tournament is already in progress.
-    //
-    // Rock Paper Scissors is a game between two players. Each game contains many rounds; in each round,
-    // the players each simultaneously choose one of Rock, Paper, or Scissors using a hand shape.
-    // Then, a winner for that round is selected: Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock.
-    // If both players choose the same shape, the round instead ends in a draw.
-    //
-    // Appreciative of your help yesterday, one Elf gives you an encrypted strategy guide (your puzzle input)
-    // that they say will be sure to help you win. "The first column is what your opponent is going to play:
-    // A for Rock, B for Paper, and C for Scissors. The second column--" Suddenly, the Elf is called away to help with someone's tent.
-    //
-    // The second column, you reason, must be what you should play in response: X for Rock, Y for Paper, and Z for Scissors.
-    // Winning every time would be suspicious, so the responses must have been carefully chosen.
-    //
-    // The winner of the whole tournament is the player with the highest score.
-    // Your total score is the sum of your scores for each round.
-    // The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors)
-    // plus the score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
-    //
-    // Since you can't be sure if the Elf is trying to help you or trick you, you should calculate the score you would get if you were to follow the strategy guide.
-    //
-    // For example, suppose you were given the following strategy guide:
-    //
-    // A Y
-    // B X
-    // C Z
-    //
-    // This strategy guide predicts and recommends the following:
-    //
-    //     In the first round, your opponent will choose Rock (A), and you should choose Paper (Y). This ends in a win for you with a score of 8 (2 because you chose Paper + 6 because you won).
-    //     In the second round, your opponent will choose Paper (B), and you should choose Rock (X). This ends in a loss for you with a score of 1 (1 + 0).
-    //     The third round is a draw with both players choosing Scissors, giving you a score of 3 + 3 = 6.
-    //
-    // In this example, if you were to follow the strategy guide, you would get a total score of 15 (8 + 1 + 6).
-    //
-    // What would your total score be if everything goes exactly according to your strategy guide?
-    //
-    // --- Part Two ---
-    //
-    // The Elf finishes helping with the tent and sneaks back over to you. "Anyway, the second column says how the round needs to end:
-    // X means you need to lose, Y means you need to end the round in a draw, and Z means you need to win. Good luck!"
-    //
-    // The total score is still calculated in the same way, but now you need to figure out what shape to choose so the round ends as indicated. The example above now goes like this:
-    //
-    //     In the first round, your opponent will choose Rock (A), and you need the round to end in a draw (Y), so you also choose Rock. This gives you a score of 1 + 3 = 4.
-    //     In the second round, your opponent will choose Paper (B), and you choose Rock so you lose (X) with a score of 1 + 0 = 1.
-    //     In the third round, you will defeat your opponent's Scissors with Rock for a score of 1 + 6 = 7.
-    //
-    // Now that you're correctly decrypting the ultra top secret strategy guide, you would get a total score of 12.
-    //
-    // Following the Elf's instructions for the second column, what would your total score be if everything goes exactly according to your strategy guide?
-    puzzle_1();
-    puzzle_2();
+pub mod analysis;
+pub mod cipher;
+pub mod crack;
+pub mod generate;
+pub mod rules;
+pub mod tournament;
+pub mod utils;
+
+use crate::day2::cipher::{Cipher, CipherOverride};
+use crate::day2::rules::Rules;
+use crate::day2::utils::{calculate_score, parse_entry, parse_input, GuideEntry};
+use crate::{
+    error::{AocError, Result},
+    normalize::non_blank_lines,
+    solution::{Part, Solution},
+};
+
+// --- Day 2: Rock Paper Scissors ---
+//
+// The Elves begin to set up camp on the beach. To decide whose tent gets to be closest to the snack storage,
+// a giant Rock Paper Scissors tournament is already in progress.
+//
+// Rock Paper Scissors is a game between two players. Each game contains many rounds; in each round,
+// the players each simultaneously choose one of Rock, Paper, or Scissors using a hand shape.
+// Then, a winner for that round is selected: Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock.
+// If both players choose the same shape, the round instead ends in a draw.
+//
+// Appreciative of your help yesterday, one Elf gives you an encrypted strategy guide (your puzzle input)
+// that they say will be sure to help you win. "The first column is what your opponent is going to play:
+// A for Rock, B for Paper, and C for Scissors. The second column--" Suddenly, the Elf is called away to help with someone's tent.
+//
+// The second column, you reason, must be what you should play in response: X for Rock, Y for Paper, and Z for Scissors.
+// Winning every time would be suspicious, so the responses must have been carefully chosen.
+//
+// The winner of the whole tournament is the player with the highest score.
+// Your total score is the sum of your scores for each round.
+// The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors)
+// plus the score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
+//
+// Since you can't be sure if the Elf is trying to help you or trick you, you should calculate the score you would get if you were to follow the strategy guide.
+//
+// For example, suppose you were given the following strategy guide:
+//
+// A Y
+// B X
+// C Z
+//
+// This strategy guide predicts and recommends the following:
+//
+//     In the first round, your opponent will choose Rock (A), and you should choose Paper (Y). This ends in a win for you with a score of 8 (2 because you chose Paper + 6 because you won).
+//     In the second round, your opponent will choose Paper (B), and you should choose Rock (X). This ends in a loss for you with a score of 1 (1 + 0).
+//     The third round is a draw with both players choosing Scissors, giving you a score of 3 + 3 = 6.
+//
+// In this example, if you were to follow the strategy guide, you would get a total score of 15 (8 + 1 + 6).
+//
+// What would your total score be if everything goes exactly according to your strategy guide?
+//
+// --- Part Two ---
+//
+// The Elf finishes helping with the tent and sneaks back over to you. "Anyway, the second column says how the round needs to end:
+// X means you need to lose, Y means you need to end the round in a draw, and Z means you need to win. Good luck!"
+//
+// The total score is still calculated in the same way, but now you need to figure out what shape to choose so the round ends as indicated. The example above now goes like this:
+//
+//     In the first round, your opponent will choose Rock (A), and you need the round to end in a draw (Y), so you also choose Rock. This gives you a score of 1 + 3 = 4.
+//     In the second round, your opponent will choose Paper (B), and you choose Rock so you lose (X) with a score of 1 + 0 = 1.
+//     In the third round, you will defeat your opponent's Scissors with Rock for a score of 1 + 6 = 7.
+//
+// Now that you're correctly decrypting the ultra top secret strategy guide, you would get a total score of 12.
+//
+// Following the Elf's instructions for the second column, what would your total score be if everything goes exactly according to your strategy guide?
+pub struct Day2 {
+    /// The game both parts play, Rock Paper Scissors by default.
+    pub rules: Rules,
+    /// How part 1 reads the guide, [`Cipher::shapes`] by default.
+    pub part1: Cipher,
+    /// How part 2 reads the guide, [`Cipher::outcomes`] by default.
+    pub part2: Cipher,
+}
+
+impl Default for Day2 {
+    fn default() -> Day2 {
+        Day2::new(Rules::default(), &CipherOverride::default())
+    }
+}
+
+impl Day2 {
+    /// Both parts playing `rules`, with the columns of `cipher_override`
+    /// swapped in.
+    pub fn new(rules: Rules, cipher_override: &CipherOverride) -> Day2 {
+        Day2 {
+            part1: Cipher::shapes(&rules).with_override(cipher_override, Part::One),
+            part2: Cipher::outcomes(&rules).with_override(cipher_override, Part::Two),
+            rules,
+        }
+    }
+}
+
+impl Solution for Day2 {
+    type Model = Vec<GuideEntry>;
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<GuideEntry>> {
+        parse_input(input)
+    }
+
+    /// Checks that every line has two columns, each a symbol of both
+    /// parts' ciphers.
+    fn validate(&self, input: &str) -> Vec<AocError> {
+        let mut violations = Vec::new();
+        let mut rounds = 0;
+        for (line, content) in non_blank_lines(input) {
+            rounds += 1;
+            let entry = match parse_entry(line, content) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    violations.push(err);
+                    continue;
+                }
+            };
+            let mut messages: Vec<String> = Vec::new();
+            for err in self
+                .part1
+                .check(&entry)
+                .into_iter()
+                .chain(self.part2.check(&entry))
+            {
+                // Both parts usually share their symbols; report each once.
+                if !messages.contains(&err.to_string()) {
+                    messages.push(err.to_string());
+                    violations.push(err);
+                }
+            }
+        }
+        if rounds == 0 {
+            violations.push(AocError::invalid_data("the guide has no rounds"));
+        }
+        violations
+    }
+
+    fn part1(&self, guide: &Vec<GuideEntry>) -> Result<String> {
+        let rounds = self.part1.decode(&self.rules, guide)?;
+        Ok(calculate_score(&self.rules, &rounds)?.to_string())
+    }
+
+    fn part2(&self, guide: &Vec<GuideEntry>) -> Result<String> {
+        let rounds = self.part2.decode(&self.rules, guide)?;
+        Ok(calculate_score(&self.rules, &rounds)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day2::utils::RoundResult, rng::Rng};
+
+    /// The puzzle's shapes and the rules below, spelled out rather than
+    /// taken from [`Rules`], so that the tests check the solver against a
+    /// second, independent reading of the puzzle.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Shape {
+        Rock,
+        Paper,
+        Scissors,
+    }
+
+    impl Shape {
+        fn name(&self) -> &'static str {
+            match self {
+                Shape::Rock => "Rock",
+                Shape::Paper => "Paper",
+                Shape::Scissors => "Scissors",
+            }
+        }
+    }
+
+    fn reference_beaten_by(shape: &Shape) -> Shape {
+        match shape {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    fn reference_result(opponent: &Shape, mine: &Shape) -> RoundResult {
+        if opponent == mine {
+            RoundResult::Draw
+        } else if reference_beaten_by(mine) == *opponent {
+            RoundResult::Win
+        } else {
+            RoundResult::Loss
+        }
+    }
+
+    fn reference_shape(opponent: &Shape, result: &RoundResult) -> Shape {
+        match result {
+            RoundResult::Draw => opponent.clone(),
+            RoundResult::Loss => reference_beaten_by(opponent),
+            RoundResult::Win => reference_beaten_by(&reference_beaten_by(opponent)),
+        }
+    }
+
+    fn reference_score(opponent: &Shape, mine: &Shape) -> u64 {
+        let shape = match mine {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        };
+        let result = match reference_result(opponent, mine) {
+            RoundResult::Loss => 0,
+            RoundResult::Draw => 3,
+            RoundResult::Win => 6,
+        };
+        shape + result
+    }
+
+    const OPPONENT: [(&str, Shape); 3] = [
+        ("A", Shape::Rock),
+        ("B", Shape::Paper),
+        ("C", Shape::Scissors),
+    ];
+    const SECOND: [(&str, Shape, RoundResult); 3] = [
+        ("X", Shape::Rock, RoundResult::Loss),
+        ("Y", Shape::Paper, RoundResult::Draw),
+        ("Z", Shape::Scissors, RoundResult::Win),
+    ];
+
+    const SEPARATORS: [&str; 4] = [" ", "  ", "\t", " \t "];
+
+    /// Both answers of `day2` for `input`.
+    fn solve(day2: &Day2, input: &str) -> (String, String) {
+        let guide = day2.parse(input).unwrap();
+        (day2.part1(&guide).unwrap(), day2.part2(&guide).unwrap())
+    }
+
+    #[test]
+    fn chosen_shape_ends_the_round_as_asked() {
+        let day2 = Day2::default();
+        for (opponent_symbol, opponent) in &OPPONENT {
+            for (second_symbol, shape, result) in &SECOND {
+                let line = format!("{} {}", opponent_symbol, second_symbol);
+                let guide = day2.parse(&line).unwrap();
+                let round = &day2.part2.decode(&day2.rules, &guide).unwrap()[0];
+                assert_eq!(
+                    day2.rules.result(round.opponent_shape, round.my_shape),
+                    *result,
+                    "{}",
+                    line
+                );
+                let mine = reference_shape(opponent, result);
+                assert_eq!(day2.rules.name(round.my_shape), mine.name(), "{}", line);
+                assert_eq!(
+                    solve(&day2, &line),
+                    (
+                        reference_score(opponent, shape).to_string(),
+                        reference_score(opponent, &mine).to_string()
+                    ),
+                    "{}",
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn random_guides_match_the_reference() {
+        let day2 = Day2::default();
+        for seed in 0..64 {
+            let mut rng = Rng::new(seed);
+            let mut input = String::new();
+            let (mut expected_1, mut expected_2) = (0, 0);
+            for _ in 0..rng.below(200) {
+                let (opponent_symbol, opponent) = rng.pick(&OPPONENT);
+                let (second_symbol, shape, result) = rng.pick(&SECOND);
+                input.push_str(opponent_symbol);
+                let separator = rng.pick(&SEPARATORS);
+                input.push_str(separator);
+                input.push_str(second_symbol);
+                input.push('\n');
+                expected_1 += reference_score(opponent, shape);
+                expected_2 += reference_score(opponent, &reference_shape(opponent, result));
+            }
+            assert_eq!(
+                solve(&day2, &input),
+                (expected_1.to_string(), expected_2.to_string()),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn validate_reports_every_unknown_symbol() {
+        let day2 = Day2::default();
+        assert!(day2.validate(include_str!("example.txt")).is_empty());
+        let violations: Vec<String> = day2
+            .validate("A Y\nD W\nB\nC Z\n")
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "line 2, column 1: unknown shape `D`, expected A, B or C",
+                "line 2, column 3: unknown second column `W`, expected X, Y or Z",
+                "line 3, column 1: expected two whitespace-separated columns, found `B`",
+            ]
+        );
+        let violations = day2.validate("\n \n");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].to_string(),
+            "invalid puzzle data: the guide has no rounds"
+        );
+    }
 }
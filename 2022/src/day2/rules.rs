@@ -0,0 +1,382 @@
+use std::{fs, path::Path};
+
+use crate::{
+    day2::utils::RoundResult,
+    error::{AocError, Result},
+};
+
+/// A shape of a game and the points for playing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    pub score: u64,
+}
+
+impl ShapeRule {
+    pub fn new(name: impl Into<String>, score: u64) -> ShapeRule {
+        ShapeRule {
+            name: name.into(),
+            score,
+        }
+    }
+}
+
+/// The rules of a Rock Paper Scissors like game: its shapes, identified by
+/// their position, and which shape beats which. Scoring and choosing a shape
+/// for a wanted result both derive from that one relation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    /// `beats[a][b]` holds when shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    /// Builds the rules from the shapes and the `(winner, loser)` pairs of
+    /// the "beats" relation, given by shape name. Shapes that are neither
+    /// equal nor related draw.
+    pub fn new(shapes: Vec<ShapeRule>, beats: &[(&str, &str)]) -> Result<Rules> {
+        if shapes.is_empty() {
+            return Err(AocError::invalid_data("a game needs at least one shape"));
+        }
+        for (index, shape) in shapes.iter().enumerate() {
+            if shapes[..index]
+                .iter()
+                .any(|other| other.name.eq_ignore_ascii_case(&shape.name))
+            {
+                return Err(AocError::invalid_data(format!(
+                    "shape `{}` is defined twice",
+                    shape.name
+                )));
+            }
+        }
+
+        let mut rules = Rules {
+            beats: vec![vec![false; shapes.len()]; shapes.len()],
+            shapes,
+        };
+        for &(winner, loser) in beats {
+            let (winner, loser) = (rules.find_named(winner)?, rules.find_named(loser)?);
+            if winner == loser {
+                return Err(AocError::invalid_data(format!(
+                    "shape `{}` cannot beat itself",
+                    rules.shapes[winner].name
+                )));
+            }
+            if rules.beats[loser][winner] {
+                return Err(AocError::invalid_data(format!(
+                    "shapes `{}` and `{}` cannot beat each other",
+                    rules.shapes[winner].name, rules.shapes[loser].name
+                )));
+            }
+            rules.beats[winner][loser] = true;
+        }
+        Ok(rules)
+    }
+
+    /// The balanced game on an odd number of shapes, where each shape beats
+    /// those an odd number of places before it, wrapping around. Three
+    /// shapes give Rock Paper Scissors.
+    pub fn cyclic(shapes: Vec<ShapeRule>) -> Result<Rules> {
+        let n = shapes.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(AocError::invalid_data(format!(
+                "a cyclic game needs an odd number of at least three shapes, found {}",
+                n
+            )));
+        }
+        let names: Vec<String> = shapes.iter().map(|shape| shape.name.clone()).collect();
+        let mut beats = Vec::new();
+        for winner in 0..n {
+            for loser in 0..n {
+                if !((winner + n - loser) % n).is_multiple_of(2) {
+                    beats.push((names[winner].as_str(), names[loser].as_str()));
+                }
+            }
+        }
+        Rules::new(shapes, &beats)
+    }
+
+    pub fn rock_paper_scissors() -> Rules {
+        Rules::cyclic(scored(&["Rock", "Paper", "Scissors"]))
+            .expect("three shapes make a cyclic game")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Rules {
+        Rules::cyclic(scored(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]))
+            .expect("five shapes make a cyclic game")
+    }
+
+    pub fn builtin(name: &str) -> Option<Rules> {
+        match name {
+            "rock-paper-scissors" => Some(Rules::rock_paper_scissors()),
+            "lizard-spock" => Some(Rules::rock_paper_scissors_lizard_spock()),
+            _ => None,
+        }
+    }
+
+    /// A built-in game by name, or else the game in the file at `arg`.
+    pub fn from_arg(arg: &str) -> Result<Rules> {
+        match Rules::builtin(arg) {
+            Some(rules) => Ok(rules),
+            None => Rules::load(Path::new(arg)),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Rules> {
+        let content = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Rules::parse(&content)
+    }
+
+    /// Reads a game with one `NAME SCORE` line per shape and one
+    /// `WINNER beats LOSER` line per pair, or instead of the latter a
+    /// `cyclic` line for the balanced game of [`Rules::cyclic`]. Lines
+    /// starting with `#` are comments:
+    ///
+    /// ```text
+    /// Rock 1
+    /// Paper 2
+    /// Scissors 3
+    /// Spock 4
+    /// Lizard 5
+    /// cyclic
+    /// ```
+    pub fn parse(content: &str) -> Result<Rules> {
+        let mut shapes = Vec::new();
+        let mut beats: Vec<(&str, &str)> = Vec::new();
+        let mut cyclic = None;
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words[..] {
+                [] => {}
+                [first, ..] if first.starts_with('#') => {}
+                ["cyclic"] => cyclic = Some(line_number),
+                [winner, "beats", loser] => beats.push((winner, loser)),
+                [name, score] => {
+                    let score = score.parse::<u64>().map_err(|_| {
+                        AocError::parse(line_number, 1, format!("bad score `{}`", score))
+                    })?;
+                    shapes.push(ShapeRule::new(name, score));
+                }
+                _ => {
+                    return Err(AocError::parse(
+                        line_number,
+                        1,
+                        "expected `NAME SCORE`, `WINNER beats LOSER` or `cyclic`",
+                    ))
+                }
+            }
+        }
+        match cyclic {
+            Some(line_number) if !beats.is_empty() => Err(AocError::parse(
+                line_number,
+                1,
+                "a cyclic game cannot also list who beats whom",
+            )),
+            Some(_) => Rules::cyclic(shapes),
+            None => Rules::new(shapes, &beats),
+        }
+    }
+
+    pub fn shapes(&self) -> &[ShapeRule] {
+        &self.shapes
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// The shape called `name`, in any case.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.shapes
+            .iter()
+            .position(|shape| shape.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self, shape: usize) -> &str {
+        &self.shapes[shape].name
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.shapes
+            .iter()
+            .map(|shape| shape.name.as_str())
+            .collect()
+    }
+
+    fn find_named(&self, name: &str) -> Result<usize> {
+        self.find(name).ok_or_else(|| {
+            AocError::invalid_data(format!(
+                "unknown shape `{}`, expected {}",
+                name,
+                alternatives(&self.names())
+            ))
+        })
+    }
+
+    pub fn beats(&self, winner: usize, loser: usize) -> bool {
+        self.beats[winner][loser]
+    }
+
+    /// How a round ends for the player of `mine` against `opponent`.
+    pub fn result(&self, opponent: usize, mine: usize) -> RoundResult {
+        if self.beats(mine, opponent) {
+            RoundResult::Win
+        } else if self.beats(opponent, mine) {
+            RoundResult::Loss
+        } else {
+            RoundResult::Draw
+        }
+    }
+
+    /// The points for playing `mine` against `opponent`: the shape score plus
+    /// 6 for a win, 3 for a draw and 0 for a loss.
+    pub fn score(&self, opponent: usize, mine: usize) -> Result<u64> {
+        self.shapes[mine]
+            .score
+            .checked_add(self.result(opponent, mine).score())
+            .ok_or_else(|| {
+                AocError::overflow(format!(
+                    "the score of {} against {}",
+                    self.name(mine),
+                    self.name(opponent)
+                ))
+            })
+    }
+
+    /// The first shape that ends a round against `opponent` with `result`.
+    pub fn choose(&self, opponent: usize, result: &RoundResult) -> Option<usize> {
+        (0..self.shapes.len()).find(|&mine| self.result(opponent, mine) == *result)
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules::rock_paper_scissors()
+    }
+}
+
+/// `a`, `a or b`, `a, b or c`...
+pub fn alternatives(items: &[&str]) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// Scores `names` 1, 2, 3... in order.
+fn scored(names: &[&str]) -> Vec<ShapeRule> {
+    names
+        .iter()
+        .zip(1..)
+        .map(|(name, score)| ShapeRule::new(*name, score))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rock_paper_scissors_relation() {
+        let rules = Rules::rock_paper_scissors();
+        let [rock, paper, scissors] = ["Rock", "Paper", "Scissors"].map(|n| rules.find(n).unwrap());
+        assert!(rules.beats(paper, rock));
+        assert!(rules.beats(scissors, paper));
+        assert!(rules.beats(rock, scissors));
+        assert_eq!(rules.score(rock, paper).unwrap(), 8);
+        assert_eq!(rules.score(paper, rock).unwrap(), 1);
+        assert_eq!(rules.score(scissors, scissors).unwrap(), 6);
+    }
+
+    #[test]
+    fn huge_shape_scores_overflow_instead_of_wrapping() {
+        let rules =
+            Rules::parse("Rock 18446744073709551615\nPaper 2\nScissors 3\ncyclic\n").unwrap();
+        let [rock, scissors] = ["Rock", "Scissors"].map(|n| rules.find(n).unwrap());
+        assert_eq!(
+            rules.score(scissors, rock).unwrap_err().to_string(),
+            "the score of Rock against Scissors does not fit in 64 bits"
+        );
+        assert_eq!(
+            rules.score(rock, rock).unwrap_err().to_string(),
+            "the score of Rock against Rock does not fit in 64 bits"
+        );
+        // A loss adds nothing, so the shape score alone still fits.
+        let paper = rules.find("Paper").unwrap();
+        assert_eq!(rules.score(paper, rock).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn lizard_spock_shapes_each_beat_two() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let shape = |name| rules.find(name).unwrap();
+        for (winner, loser) in [
+            ("Spock", "Scissors"),
+            ("Spock", "Rock"),
+            ("Lizard", "Spock"),
+            ("Lizard", "Paper"),
+            ("Rock", "Lizard"),
+            ("Scissors", "Lizard"),
+            ("Paper", "Spock"),
+        ] {
+            assert!(rules.beats(shape(winner), shape(loser)), "{}", winner);
+        }
+        for winner in 0..5 {
+            assert_eq!(
+                (0..5).filter(|&loser| rules.beats(winner, loser)).count(),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn choose_gives_the_wanted_result() {
+        for rules in [
+            Rules::rock_paper_scissors(),
+            Rules::rock_paper_scissors_lizard_spock(),
+        ] {
+            for opponent in 0..rules.shapes().len() {
+                for result in [RoundResult::Win, RoundResult::Draw, RoundResult::Loss] {
+                    let mine = rules.choose(opponent, &result).unwrap();
+                    assert_eq!(rules.result(opponent, mine), result);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_reads_listed_and_cyclic_games() {
+        let listed = "# the puzzle's game\nRock 1\nPaper 2\nScissors 3\n\nPaper beats Rock\nScissors beats Paper\nRock beats Scissors\n";
+        assert_eq!(Rules::parse(listed).unwrap(), Rules::rock_paper_scissors());
+        let cyclic = "Rock 1\nPaper 2\nScissors 3\nSpock 4\nLizard 5\ncyclic\n";
+        assert_eq!(
+            Rules::parse(cyclic).unwrap(),
+            Rules::rock_paper_scissors_lizard_spock()
+        );
+        assert_eq!(
+            Rules::parse("Rock one\n").unwrap_err().to_string(),
+            "line 1, column 1: bad score `one`"
+        );
+        assert!(Rules::parse("Rock 1\nPaper 2\nScissors 3\ncyclic\nRock beats Paper\n").is_err());
+        assert_eq!(Rules::default().find("paper"), Some(1));
+    }
+
+    #[test]
+    fn new_rejects_inconsistent_relations() {
+        let shapes = || scored(&["Rock", "Paper"]);
+        assert!(Rules::new(shapes(), &[("Rock", "Rock")]).is_err());
+        assert!(Rules::new(shapes(), &[("Rock", "Paper"), ("Paper", "Rock")]).is_err());
+        assert!(Rules::new(shapes(), &[("Rock", "Well")]).is_err());
+        assert!(Rules::cyclic(scored(&["A", "B", "C", "D"])).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::{
+    day2::{
+        rules::Rules,
+        utils::{Round, RoundResult},
+    },
+    error::{AocError, Result},
+};
+
+/// How one side of a game fared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: u64,
+}
+
+impl Tally {
+    fn record(&mut self, result: &RoundResult, score: u64) -> Result<()> {
+        match result {
+            RoundResult::Win => self.wins += 1,
+            RoundResult::Draw => self.draws += 1,
+            RoundResult::Loss => self.losses += 1,
+        }
+        self.score = self
+            .score
+            .checked_add(score)
+            .ok_or_else(|| AocError::overflow("the tournament score"))?;
+        Ok(())
+    }
+}
+
+/// A game of many rounds between two sides, each scored by the puzzle rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub first: Tally,
+    pub second: Tally,
+    /// Both running scores after each round.
+    pub progression: Vec<(u64, u64)>,
+}
+
+impl Game {
+    /// How the game ended for the first side: the higher total score wins.
+    pub fn result(&self) -> RoundResult {
+        match self.first.score.cmp(&self.second.score) {
+            Ordering::Greater => RoundResult::Win,
+            Ordering::Equal => RoundResult::Draw,
+            Ordering::Less => RoundResult::Loss,
+        }
+    }
+}
+
+/// Plays `first` against `second` move by move, working out each result
+/// from `rules` rather than trusting a recorded one. Both sides must play
+/// as many moves.
+pub fn play(rules: &Rules, first: &[usize], second: &[usize]) -> Result<Game> {
+    if first.len() != second.len() {
+        return Err(AocError::invalid_data(format!(
+            "the first side plays {} moves but the second {}",
+            first.len(),
+            second.len()
+        )));
+    }
+    let mut game = Game {
+        first: Tally::default(),
+        second: Tally::default(),
+        progression: Vec::with_capacity(first.len()),
+    };
+    for (&first_shape, &second_shape) in first.iter().zip(second) {
+        game.first.record(
+            &rules.result(second_shape, first_shape),
+            rules.score(second_shape, first_shape)?,
+        )?;
+        game.second.record(
+            &rules.result(first_shape, second_shape),
+            rules.score(first_shape, second_shape)?,
+        )?;
+        game.progression.push((game.first.score, game.second.score));
+    }
+    Ok(game)
+}
+
+/// Plays a decoded guide, with me as the first side and the opponent second.
+pub fn play_guide(rules: &Rules, rounds: &[Round]) -> Result<Game> {
+    let mine: Vec<usize> = rounds.iter().map(|round| round.my_shape).collect();
+    let theirs: Vec<usize> = rounds.iter().map(|round| round.opponent_shape).collect();
+    play(rules, &mine, &theirs)
+}
+
+/// A named sequence of moves, such as the shapes a guide tells me to play,
+/// given by their position in the [`Rules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub moves: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    /// Games won, drawn and lost, with the points of every round added up.
+    pub games: Tally,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundRobin {
+    /// The names of the players, in the order they were given.
+    pub players: Vec<String>,
+    /// Every pairing, as the indices of both players and their game.
+    pub games: Vec<(usize, usize, Game)>,
+    /// Most games won first, then most points.
+    pub standings: Vec<Standing>,
+}
+
+/// Plays every player once against every other, who must all play as many
+/// moves.
+pub fn round_robin(rules: &Rules, players: &[Player]) -> Result<RoundRobin> {
+    if let Some((first, other)) = players.first().and_then(|first| {
+        players
+            .iter()
+            .find(|player| player.moves.len() != first.moves.len())
+            .map(|other| (first, other))
+    }) {
+        return Err(AocError::invalid_data(format!(
+            "`{}` plays {} moves but `{}` {}",
+            first.name,
+            first.moves.len(),
+            other.name,
+            other.moves.len()
+        )));
+    }
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name.clone(),
+            games: Tally::default(),
+        })
+        .collect();
+    let mut games = Vec::new();
+    for first in 0..players.len() {
+        for second in first + 1..players.len() {
+            let game = play(rules, &players[first].moves, &players[second].moves)?;
+            let first_result = game.result();
+            let second_result = match first_result {
+                RoundResult::Win => RoundResult::Loss,
+                RoundResult::Draw => RoundResult::Draw,
+                RoundResult::Loss => RoundResult::Win,
+            };
+            standings[first]
+                .games
+                .record(&first_result, game.first.score)?;
+            standings[second]
+                .games
+                .record(&second_result, game.second.score)?;
+            games.push((first, second, game));
+        }
+    }
+    standings.sort_by_key(|standing| Reverse((standing.games.wins, standing.games.score)));
+    Ok(RoundRobin {
+        players: players.iter().map(|player| player.name.clone()).collect(),
+        games,
+        standings,
+    })
+}
+
+fn result_name(result: &RoundResult) -> &'static str {
+    match result {
+        RoundResult::Win => "won",
+        RoundResult::Draw => "drew",
+        RoundResult::Loss => "lost",
+    }
+}
+
+/// Prints the tally of both sides, the winner and, with `progression`, the
+/// running scores after every round.
+pub fn print_game(game: &Game, names: [&str; 2], progression: bool) {
+    let width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{:<width$}  {:>6}  {:>6}  {:>6}  {:>8}",
+        "",
+        "Wins",
+        "Draws",
+        "Losses",
+        "Score",
+        width = width
+    );
+    for (name, tally) in names.iter().zip([&game.first, &game.second]) {
+        println!(
+            "{:<width$}  {:>6}  {:>6}  {:>6}  {:>8}",
+            name,
+            tally.wins,
+            tally.draws,
+            tally.losses,
+            tally.score,
+            width = width
+        );
+    }
+    match game.result() {
+        RoundResult::Win => println!("\n{} wins", names[0]),
+        RoundResult::Draw => println!("\nThe game is a draw"),
+        RoundResult::Loss => println!("\n{} wins", names[1]),
+    }
+
+    if progression {
+        println!("\n{:>6}  {:>8}  {:>8}", "Round", names[0], names[1]);
+        for (round, (first, second)) in game.progression.iter().enumerate() {
+            println!("{:>6}  {:>8}  {:>8}", round + 1, first, second);
+        }
+    }
+}
+
+pub fn print_round_robin(round_robin: &RoundRobin) {
+    let width = round_robin
+        .standings
+        .iter()
+        .map(|standing| standing.name.len())
+        .max()
+        .unwrap_or_default();
+    for (first, second, game) in &round_robin.games {
+        println!(
+            "{:<width$} {:>4} {:<width$}  {:>8} - {}",
+            round_robin.players[*first],
+            result_name(&game.result()),
+            round_robin.players[*second],
+            game.first.score,
+            game.second.score,
+            width = width
+        );
+    }
+
+    println!(
+        "\n{:<width$}  {:>4}  {:>5}  {:>6}  {:>10}",
+        "Player",
+        "Won",
+        "Drawn",
+        "Lost",
+        "Points",
+        width = width.max("Player".len())
+    );
+    for standing in &round_robin.standings {
+        println!(
+            "{:<width$}  {:>4}  {:>5}  {:>6}  {:>10}",
+            standing.name,
+            standing.games.wins,
+            standing.games.draws,
+            standing.games.losses,
+            standing.games.score,
+            width = width.max("Player".len())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::{cipher::Cipher, utils::parse_input};
+
+    #[test]
+    fn play_guide_scores_both_sides() {
+        let rules = Rules::rock_paper_scissors();
+        let guide = parse_input(include_str!("example.txt")).unwrap();
+        let rounds = Cipher::shapes(&rules).decode(&rules, &guide).unwrap();
+        let game = play_guide(&rules, &rounds).unwrap();
+        assert_eq!(
+            game.first,
+            Tally {
+                wins: 1,
+                draws: 1,
+                losses: 1,
+                score: 15
+            }
+        );
+        assert_eq!(game.second.score, 15);
+        assert_eq!(game.progression, vec![(8, 1), (9, 9), (15, 15)]);
+        assert_eq!(game.result(), RoundResult::Draw);
+    }
+
+    #[test]
+    fn round_robin_ranks_players() {
+        let rules = Rules::rock_paper_scissors();
+        let player = |name: &str| Player {
+            name: name.to_lowercase(),
+            moves: vec![rules.find(name).unwrap(); 2],
+        };
+        let players = [player("Rock"), player("Paper"), player("Scissors")];
+        let round_robin = round_robin(&rules, &players).unwrap();
+        assert_eq!(round_robin.games.len(), 3);
+        let names: Vec<&str> = round_robin
+            .standings
+            .iter()
+            .map(|standing| standing.name.as_str())
+            .collect();
+        // Each wins once; paper and scissors score more for their shapes.
+        assert_eq!(names, vec!["scissors", "paper", "rock"]);
+        assert!(round_robin
+            .standings
+            .iter()
+            .all(|standing| standing.games.wins == 1 && standing.games.losses == 1));
+    }
+
+    #[test]
+    fn sides_must_play_as_many_moves() {
+        let rules = Rules::rock_paper_scissors();
+        let err = play(&rules, &[0, 1, 2], &[0, 1]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid puzzle data: the first side plays 3 moves but the second 2"
+        );
+
+        let players = [
+            Player {
+                name: "a.txt".to_string(),
+                moves: vec![0; 3],
+            },
+            Player {
+                name: "b.txt".to_string(),
+                moves: vec![1; 3],
+            },
+            Player {
+                name: "c.txt".to_string(),
+                moves: vec![2; 4],
+            },
+        ];
+        let err = round_robin(&rules, &players).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid puzzle data: `a.txt` plays 3 moves but `c.txt` 4"
+        );
+    }
+}
@@ -1,67 +1,162 @@
-use std::fs;
+use crate::{
+    day2::rules::Rules,
+    error::{AocError, Result},
+    normalize::non_blank_lines,
+};
 
-#[derive(Debug, Clone)]
-pub enum Shape {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RoundResult {
     Win,
     Draw,
     Loss,
 }
 
-#[derive(Debug)]
+impl RoundResult {
+    pub fn score(&self) -> u64 {
+        match self {
+            RoundResult::Win => 6,
+            RoundResult::Draw => 3,
+            RoundResult::Loss => 0,
+        }
+    }
+}
+
+/// A round of a game, with both shapes given by their position in its
+/// [`Rules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Round {
-    pub opponent_shape: Shape,
-    pub my_shape: Shape,
-    pub result: RoundResult,
+    pub opponent_shape: usize,
+    pub my_shape: usize,
 }
 
 impl Round {
-    pub fn new(opponent_shape: Shape, my_shape: Shape, result: RoundResult) -> Round {
+    pub fn new(opponent_shape: usize, my_shape: usize) -> Round {
         Round {
             opponent_shape,
             my_shape,
-            result,
         }
     }
 }
 
-pub fn read_input() -> String {
-    return fs::read_to_string("src/day2/input.txt")
-        .expect("Something went wrong reading the file");
+/// A symbol of the strategy guide and the 1-based column it starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub column: usize,
+    pub text: String,
+}
+
+/// A line of the strategy guide, before a [`Cipher`](crate::day2::cipher::Cipher)
+/// gives its symbols a meaning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuideEntry {
+    pub line: usize,
+    pub opponent: Symbol,
+    pub second: Symbol,
+}
+
+/// Splits a strategy guide line on any run of whitespace into its two
+/// columns, each paired with its 1-based column.
+pub fn split_columns(line: usize, content: &str) -> Result<[(usize, &str); 2]> {
+    let columns: Vec<(usize, &str)> = content
+        .split_whitespace()
+        .map(|column| {
+            // `column` borrows from `content`, so the pointers give its offset.
+            let offset = column.as_ptr() as usize - content.as_ptr() as usize;
+            (content[..offset].chars().count() + 1, column)
+        })
+        .collect();
+    match columns[..] {
+        [first, second] => Ok([first, second]),
+        _ => Err(AocError::parse(
+            line,
+            1,
+            format!(
+                "expected two whitespace-separated columns, found `{}`",
+                content
+            ),
+        )),
+    }
+}
+
+pub fn parse_entry(line: usize, content: &str) -> Result<GuideEntry> {
+    let [opponent, second] = split_columns(line, content)?.map(|(column, text)| Symbol {
+        column,
+        text: text.to_string(),
+    });
+    Ok(GuideEntry {
+        line,
+        opponent,
+        second,
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<GuideEntry>> {
+    let mut guide: Vec<GuideEntry> = Vec::new();
+    for (line, content) in non_blank_lines(input) {
+        guide.push(parse_entry(line, content)?);
+    }
+    Ok(guide)
 }
 
-pub fn calculate_score(rounds: Vec<Round>) -> i32 {
-    let mut score = 0;
+pub fn calculate_score(rules: &Rules, rounds: &[Round]) -> Result<u64> {
+    let mut score: u64 = 0;
     for round in rounds {
-        let round_score = match round.opponent_shape {
-            Shape::Rock => match round.my_shape {
-                Shape::Rock => 3,
-                Shape::Paper => 6,
-                Shape::Scissors => 0,
-            },
-            Shape::Paper => match round.my_shape {
-                Shape::Rock => 0,
-                Shape::Paper => 3,
-                Shape::Scissors => 6,
-            },
-            Shape::Scissors => match round.my_shape {
-                Shape::Rock => 6,
-                Shape::Paper => 0,
-                Shape::Scissors => 3,
-            },
-        };
-        let shape_score = match round.my_shape {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
+        score = score
+            .checked_add(rules.score(round.opponent_shape, round.my_shape)?)
+            .ok_or_else(|| AocError::overflow("the total score"))?;
+    }
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::normalize;
+
+    #[test]
+    fn calculate_score_adds_shape_and_outcome() {
+        let rules = Rules::rock_paper_scissors();
+        let score = |opponent, mine| {
+            let [opponent, mine] = [opponent, mine].map(|name| rules.find(name).unwrap());
+            calculate_score(&rules, &[Round::new(opponent, mine)]).unwrap()
         };
-        score += round_score + shape_score;
+        assert_eq!(score("Rock", "Paper"), 8);
+        assert_eq!(score("Paper", "Rock"), 1);
+        assert_eq!(score("Scissors", "Scissors"), 6);
+
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let [spock, lizard] = ["Spock", "Lizard"].map(|name| rules.find(name).unwrap());
+        let rounds = [Round::new(spock, lizard), Round::new(lizard, lizard)];
+        assert_eq!(calculate_score(&rules, &rounds).unwrap(), 5 + 6 + 5 + 3);
+    }
+
+    #[test]
+    fn parse_input_example() {
+        let guide = parse_input(include_str!("example.txt")).unwrap();
+        let symbols: Vec<(&str, &str)> = guide
+            .iter()
+            .map(|entry| (entry.opponent.text.as_str(), entry.second.text.as_str()))
+            .collect();
+        assert_eq!(symbols, vec![("A", "Y"), ("B", "X"), ("C", "Z")]);
+        assert_eq!((guide[2].line, guide[2].second.column), (3, 3));
+    }
+
+    #[test]
+    fn parse_input_skips_blank_lines_left_by_normalization() {
+        let normalized = normalize("\u{feff}A Y\r\nB X\r\n\r\nC Z\r\n");
+        let guide = parse_input(normalized.text()).unwrap();
+        let lines: Vec<usize> = guide
+            .iter()
+            .map(|entry| normalized.original_line(entry.line))
+            .collect();
+        assert_eq!(lines, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn split_columns_reports_missing_column() {
+        assert_eq!(split_columns(1, "A Y").unwrap(), [(1, "A"), (3, "Y")]);
+        assert_eq!(split_columns(2, " B\t Z").unwrap(), [(2, "B"), (5, "Z")]);
+        let err = split_columns(4, "A").unwrap_err();
+        assert!(matches!(err, AocError::Parse { line: 4, .. }), "{:?}", err);
     }
-    score
 }
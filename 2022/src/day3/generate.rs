@@ -0,0 +1,84 @@
+use crate::{
+    error::{AocError, Result},
+    generate::Generated,
+    rng::Rng,
+};
+
+/// As many rucksacks as in the checked-in input.
+pub const DEFAULT_RUCKSACKS: usize = 300;
+/// The most items a generated compartment holds.
+const MAX_COMPARTMENT: u64 = 16;
+
+/// The puzzle's items, in priority order.
+fn letters() -> Vec<char> {
+    ('a'..='z').chain('A'..='Z').collect()
+}
+
+/// `rucksacks` rucksacks in groups of three. Each group has one badge, and
+/// each rucksack one item in both compartments: the other items of a group
+/// are split between its rucksacks, and those of a rucksack between its
+/// compartments, so that nothing else is shared. The answers are the
+/// priorities of the planted items.
+pub fn generate(rng: &mut Rng, rucksacks: usize) -> Result<Generated> {
+    if rucksacks == 0 || !rucksacks.is_multiple_of(3) {
+        return Err(AocError::invalid_data(format!(
+            "day 3 needs a positive multiple of three rucksacks, not {}",
+            rucksacks
+        )));
+    }
+    let letters = letters();
+    let priority = |item: char| letters.iter().position(|&c| c == item).unwrap() as u64 + 1;
+    let mut input = String::new();
+    let mut answers = [0, 0];
+    for _ in 0..rucksacks / 3 {
+        let mut others = letters.clone();
+        let badge = others.remove(rng.below(others.len() as u64) as usize);
+        answers[1] += priority(badge);
+        rng.shuffle(&mut others);
+
+        // 51 items, 17 for each rucksack of the group.
+        for items in others.chunks(others.len() / 3) {
+            let (shared, rest) = items.split_first().expect("17 items per rucksack");
+            let (first_only, second_only) = rest.split_at(rest.len() / 2);
+            answers[0] += priority(*shared);
+
+            let size = rng.between(3, MAX_COMPARTMENT) as usize;
+            let badge_in_first = rng.below(2) == 0;
+            let mut compartments = [vec![*shared], vec![*shared]];
+            compartments[if badge_in_first { 0 } else { 1 }].push(badge);
+            for (compartment, only) in compartments.iter_mut().zip([first_only, second_only]) {
+                while compartment.len() < size {
+                    compartment.push(*rng.pick(only));
+                }
+                rng.shuffle(compartment);
+                input.extend(compartment.iter());
+            }
+            input.push('\n');
+        }
+    }
+    Ok(Generated { input, answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day3::Day3, solution::Solution};
+
+    #[test]
+    fn generated_input_keeps_the_guarantees() {
+        let generated = generate(&mut Rng::new(5), 60).unwrap();
+        let day3 = Day3::default();
+        assert!(day3.validate(&generated.input).is_empty());
+        let inventory = day3.parse(&generated.input).unwrap();
+        assert_eq!(inventory.rucksacks.len(), 60);
+        assert_eq!(
+            day3.part1(&inventory).unwrap(),
+            generated.answers[0].to_string()
+        );
+        assert_eq!(
+            day3.part2(&inventory).unwrap(),
+            generated.answers[1].to_string()
+        );
+        assert!(generate(&mut Rng::new(5), 4).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+use std::slice;
+
+/// A set of item types, identified by their number in a
+/// [`Priorities`](super::priorities::Priorities) scheme and stored as a
+/// bitmask, one bit per number. Intersections and unions work a word at a
+/// time, and iteration goes in number order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet {
+    bits: Bits,
+}
+
+/// Bit `n % 64` of word `n / 64` is set for item number `n`. A set of
+/// numbers below 64, such as any of the puzzle's letters, stays in one
+/// inline word; a larger one spills to the heap, with no trailing zero
+/// words, so that equal sets compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Bits {
+    Inline(u64),
+    Spilled(Vec<u64>),
+}
+
+impl Default for Bits {
+    fn default() -> Bits {
+        Bits::Inline(0)
+    }
+}
+
+impl ItemSet {
+    pub fn new() -> ItemSet {
+        ItemSet::default()
+    }
+
+    /// The set of the bits in `words`, inline when they fit in one.
+    fn from_words(mut words: Vec<u64>) -> ItemSet {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        let bits = match words.len() {
+            0 | 1 => Bits::Inline(words.first().copied().unwrap_or_default()),
+            _ => Bits::Spilled(words),
+        };
+        ItemSet { bits }
+    }
+
+    /// The words of the set, with no trailing zero words.
+    fn words(&self) -> &[u64] {
+        match &self.bits {
+            Bits::Inline(0) => &[],
+            Bits::Inline(word) => slice::from_ref(word),
+            Bits::Spilled(words) => words,
+        }
+    }
+
+    /// The bits of item numbers below 64.
+    fn first_word(&self) -> u64 {
+        match &self.bits {
+            Bits::Inline(word) => *word,
+            Bits::Spilled(words) => words[0],
+        }
+    }
+
+    /// Adds item `number`, returning whether it was not already present.
+    pub fn insert(&mut self, number: usize) -> bool {
+        let (word, bit) = (number / 64, 1 << (number % 64));
+        match &mut self.bits {
+            Bits::Inline(inline) if word == 0 => {
+                let added = *inline & bit == 0;
+                *inline |= bit;
+                added
+            }
+            Bits::Inline(inline) => {
+                let mut words = vec![0; word + 1];
+                words[0] = *inline;
+                words[word] = bit;
+                self.bits = Bits::Spilled(words);
+                true
+            }
+            Bits::Spilled(words) => {
+                if word >= words.len() {
+                    words.resize(word + 1, 0);
+                }
+                let added = words[word] & bit == 0;
+                words[word] |= bit;
+                added
+            }
+        }
+    }
+
+    pub fn contains(&self, number: usize) -> bool {
+        self.words()
+            .get(number / 64)
+            .is_some_and(|word| word & (1 << (number % 64)) != 0)
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        match (&self.bits, &other.bits) {
+            (Bits::Spilled(words), Bits::Spilled(others)) => {
+                ItemSet::from_words(words.iter().zip(others).map(|(a, b)| a & b).collect())
+            }
+            _ => ItemSet {
+                bits: Bits::Inline(self.first_word() & other.first_word()),
+            },
+        }
+    }
+
+    /// Keeps only the items also in `other`, without allocating.
+    pub fn intersect_with(&mut self, other: &ItemSet) {
+        match (&mut self.bits, &other.bits) {
+            (Bits::Inline(word), _) => *word &= other.first_word(),
+            (Bits::Spilled(words), Bits::Inline(other)) => {
+                self.bits = Bits::Inline(words[0] & other);
+            }
+            (Bits::Spilled(words), Bits::Spilled(others)) => {
+                words.truncate(others.len());
+                for (word, other) in words.iter_mut().zip(others) {
+                    *word &= other;
+                }
+                while words.last() == Some(&0) {
+                    words.pop();
+                }
+                if words.len() < 2 {
+                    self.bits = Bits::Inline(words.first().copied().unwrap_or_default());
+                }
+            }
+        }
+    }
+
+    /// The lowest item number in both sets, without building their
+    /// intersection.
+    pub fn first_shared(&self, other: &ItemSet) -> Option<usize> {
+        if let (Bits::Inline(a), Bits::Inline(b)) = (&self.bits, &other.bits) {
+            let shared = a & b;
+            return (shared != 0).then(|| shared.trailing_zeros() as usize);
+        }
+        self.words()
+            .iter()
+            .zip(other.words())
+            .enumerate()
+            .find(|(_, (a, b))| *a & *b != 0)
+            .map(|(index, (a, b))| index * 64 + (a & b).trailing_zeros() as usize)
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        if let (Bits::Inline(a), Bits::Inline(b)) = (&self.bits, &other.bits) {
+            return ItemSet {
+                bits: Bits::Inline(a | b),
+            };
+        }
+        let (longer, shorter) = if self.words().len() >= other.words().len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words().to_vec();
+        for (word, other) in words.iter_mut().zip(shorter.words()) {
+            *word |= other;
+        }
+        ItemSet::from_words(words)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words().is_empty()
+    }
+
+    /// The lowest item number, if any.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// The item numbers of the set, lowest first.
+    pub fn iter(&self) -> Iter<'_> {
+        let words = self.words();
+        Iter {
+            words,
+            offset: 0,
+            current: words.first().copied().unwrap_or_default(),
+        }
+    }
+}
+
+impl FromIterator<usize> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(numbers: I) -> ItemSet {
+        let mut set = ItemSet::new();
+        for number in numbers {
+            set.insert(number);
+        }
+        set
+    }
+}
+
+/// The item numbers of an [`ItemSet`], lowest first.
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    words: &'a [u64],
+    /// The number of the first bit of `current`.
+    offset: usize,
+    /// What is left of the word at `offset`.
+    current: u64,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.offset += 64;
+            self.current = *self.words.get(self.offset / 64)?;
+        }
+        let number = self.offset + self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(number)
+    }
+}
+
+impl<'a> IntoIterator for &'a ItemSet {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let first: ItemSet = [1, 5, 64, 200].into_iter().collect();
+        let second: ItemSet = [5, 64, 70].into_iter().collect();
+        let shared = first.intersection(&second);
+        assert_eq!(shared.iter().collect::<Vec<_>>(), vec![5, 64]);
+
+        let all = first.union(&second);
+        assert_eq!(all.iter().collect::<Vec<_>>(), vec![1, 5, 64, 70, 200]);
+        assert_eq!(all.len(), 5);
+        assert!(all.contains(200) && !all.contains(199) && !all.contains(1000));
+        assert_eq!(all, second.union(&first));
+
+        assert_eq!(first.first_shared(&second), Some(5));
+        let mut in_place = all.clone();
+        in_place.intersect_with(&first);
+        assert_eq!(in_place, first);
+        in_place.intersect_with(&second);
+        assert_eq!(in_place, shared);
+    }
+
+    #[test]
+    fn sets_move_between_inline_and_spilled_words() {
+        let low: ItemSet = [3, 40].into_iter().collect();
+        let mut high = low.clone();
+        assert!(high.insert(100));
+        assert_eq!(high.iter().collect::<Vec<_>>(), vec![3, 40, 100]);
+        assert_eq!(high.first_shared(&low), Some(3));
+        assert_eq!(low.first_shared(&high), Some(3));
+        assert_eq!(high.union(&low), high);
+
+        // Dropping the spilled items makes the set equal to, and hash like,
+        // one that never spilled.
+        assert_eq!(high.intersection(&low), low);
+        let mut shrunk = high.clone();
+        shrunk.intersect_with(&low);
+        assert_eq!(shrunk, low);
+        let mut shrunk = high.clone();
+        shrunk.intersect_with(&[3, 40, 200].into_iter().collect());
+        assert_eq!(shrunk, low);
+        assert_eq!(shrunk.bits, Bits::Inline((1 << 3) | (1 << 40)));
+    }
+
+    #[test]
+    fn empty_sets_are_equal() {
+        let high: ItemSet = [130].into_iter().collect();
+        let low: ItemSet = [2].into_iter().collect();
+        let none = high.intersection(&low);
+        assert!(none.is_empty());
+        assert_eq!(none, ItemSet::new());
+        assert_eq!(none.first(), None);
+
+        let mut set = ItemSet::new();
+        assert!(set.insert(51));
+        assert!(!set.insert(51));
+        assert_eq!(set.first(), Some(51));
+    }
+}
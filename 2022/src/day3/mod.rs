@@ -1,205 +1,650 @@
-use std::fs;
+pub mod generate;
+pub mod item_set;
+pub mod priorities;
+pub mod repack;
+pub mod strings;
 
-const PRIORITIES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+use std::{fmt, ops::Range};
+
+use crate::{
+    day3::{item_set::ItemSet, priorities::Priorities},
+    error::{AocError, Result},
+    normalize::non_blank_lines,
+    solution::Solution,
+};
 
 #[derive(Debug, Clone)]
-struct Rucksack {
+pub struct Rucksack {
     first_compartment: String,
     second_compartment: String,
+    first_items: ItemSet,
+    second_items: ItemSet,
+    /// Both compartments together.
+    items: ItemSet,
 }
 
 impl Rucksack {
-    fn new(first_compartment: String, second_compartment: String) -> Rucksack {
-        Rucksack {
-            first_compartment,
-            second_compartment,
+    pub fn compartments(&self) -> (&str, &str) {
+        (&self.first_compartment, &self.second_compartment)
+    }
+
+    /// Every item type in the rucksack, in either compartment.
+    pub fn items(&self) -> &ItemSet {
+        &self.items
+    }
+
+    /// The item types found in both compartments.
+    pub fn shared_items(&self) -> ItemSet {
+        self.first_items.intersection(&self.second_items)
+    }
+
+    fn compartment_intersection(&self) -> Option<usize> {
+        self.first_items.first_shared(&self.second_items)
+    }
+}
+
+impl fmt::Display for Rucksack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.first_compartment, self.second_compartment)
+    }
+}
+
+/// The rucksacks of a list, with the priority scheme their items were
+/// numbered by.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    pub priorities: Priorities,
+    pub rucksacks: Vec<Rucksack>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RucksackGroup {
+    rucksacks: Vec<Rucksack>,
+}
+
+impl RucksackGroup {
+    fn new(rucksacks: Vec<Rucksack>) -> RucksackGroup {
+        RucksackGroup { rucksacks }
+    }
+
+    pub fn rucksacks(&self) -> &[Rucksack] {
+        &self.rucksacks
+    }
+
+    /// The item types carried by every rucksack of the group.
+    pub fn badge_items(&self) -> ItemSet {
+        let Some((first, others)) = self.rucksacks.split_first() else {
+            return ItemSet::new();
+        };
+        let mut badges = first.items.clone();
+        for rucksack in others {
+            badges.intersect_with(&rucksack.items);
+        }
+        badges
+    }
+
+    fn badge_intersection(&self) -> Option<usize> {
+        self.badge_items().first()
+    }
+}
+
+/// What to do with the rucksacks left over when their count is not a
+/// multiple of the group size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Remainder {
+    /// Refuse to group them.
+    #[default]
+    Reject,
+    /// Leave them out of every group.
+    Drop,
+    /// Put them in a last, smaller group.
+    Keep,
+}
+
+/// How part 2 gathers the rucksacks into groups looking for a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// Consecutive groups of `size` rucksacks, each rucksack in one group.
+    Chunks { size: usize, remainder: Remainder },
+    /// Every run of `size` consecutive rucksacks, each starting one
+    /// rucksack after the previous one.
+    Windows { size: usize },
+}
+
+impl Default for Grouping {
+    fn default() -> Grouping {
+        Grouping::Chunks {
+            size: 3,
+            remainder: Remainder::Reject,
         }
     }
+}
 
-    fn to_string(&self) -> String {
-        format!("{}{}", self.first_compartment, self.second_compartment)
+/// Reads the puzzle's rucksacks, whose items are letters.
+pub fn parse_input(input: &str) -> Result<Inventory> {
+    parse_inventory(input, &Priorities::letters())
+}
+
+/// Reads one rucksack per line, its first half of items in the first
+/// compartment and the rest in the second. Any character that is not an
+/// item of `priorities` is an [`AocError::UnknownItem`].
+pub fn parse_inventory(input: &str, priorities: &Priorities) -> Result<Inventory> {
+    let mut priorities = priorities.clone();
+    let mut rucksacks: Vec<Rucksack> = Vec::new();
+    for (line_number, line) in non_blank_lines(input) {
+        rucksacks.push(parse_rucksack(line_number, line, &mut priorities)?);
     }
+    Ok(Inventory {
+        priorities,
+        rucksacks,
+    })
+}
 
-    fn compartment_intersection(&self) -> Option<char> {
-        for c in self.first_compartment.chars() {
-            if self.second_compartment.contains(c) {
-                return Some(c);
+fn parse_rucksack(line_number: usize, line: &str, priorities: &mut Priorities) -> Result<Rucksack> {
+    let middle = line.chars().count() / 2;
+    let (mut first_items, mut second_items) = (ItemSet::new(), ItemSet::new());
+    let mut middle_offset = line.len();
+    for (column, (offset, item)) in line.char_indices().enumerate() {
+        let number = priorities.intern(item).ok_or(AocError::UnknownItem {
+            line: line_number,
+            column: column + 1,
+            item,
+        })?;
+        if column < middle {
+            first_items.insert(number);
+        } else {
+            if column == middle {
+                middle_offset = offset;
             }
+            second_items.insert(number);
         }
-        return None;
     }
+    let (first_compartment, second_compartment) = line.split_at(middle_offset);
+    Ok(Rucksack {
+        first_compartment: first_compartment.to_string(),
+        second_compartment: second_compartment.to_string(),
+        items: first_items.union(&second_items),
+        first_items,
+        second_items,
+    })
 }
 
-#[derive(Debug)]
-struct RucksackGroup {
-    rucksacks: (Rucksack, Rucksack, Rucksack),
+/// Lists the items of `set` as text, such as "no item types" or
+/// "2 item types, `aB`".
+fn describe(set: &ItemSet, priorities: &Priorities, what: &str) -> String {
+    if set.is_empty() {
+        return format!("no {}", what);
+    }
+    let items: String = set.iter().map(|number| priorities.item(number)).collect();
+    format!("{} {}, `{}`", set.len(), what, items)
 }
 
-impl RucksackGroup {
-    fn new(rucksacks: (Rucksack, Rucksack, Rucksack)) -> RucksackGroup {
-        RucksackGroup { rucksacks }
+/// Checks what the story guarantees, reporting every offence: each line is
+/// an even number of items of the priority scheme, exactly one item type is
+/// in both compartments, and each group has exactly one badge.
+pub fn validate(input: &str, priorities: &Priorities, grouping: &Grouping) -> Vec<AocError> {
+    let mut priorities = priorities.clone();
+    let mut violations = Vec::new();
+    // The rucksack of each line, unless it has unknown items.
+    let mut rucksacks: Vec<Option<Rucksack>> = Vec::new();
+    let mut line_numbers: Vec<usize> = Vec::new();
+    for (line_number, line) in non_blank_lines(input) {
+        line_numbers.push(line_number);
+        let items = line.chars().count();
+        if !items.is_multiple_of(2) {
+            violations.push(AocError::parse(
+                line_number,
+                1,
+                format!(
+                    "{} items cannot be split evenly between two compartments",
+                    items
+                ),
+            ));
+        }
+        let unknown: Vec<AocError> = line
+            .chars()
+            .enumerate()
+            .filter(|(_, item)| priorities.intern(*item).is_none())
+            .map(|(column, item)| AocError::UnknownItem {
+                line: line_number,
+                column: column + 1,
+                item,
+            })
+            .collect();
+        if !unknown.is_empty() {
+            violations.extend(unknown);
+            rucksacks.push(None);
+            continue;
+        }
+
+        let rucksack =
+            parse_rucksack(line_number, line, &mut priorities).expect("every item is known");
+        let shared = rucksack.shared_items();
+        if shared.len() != 1 {
+            violations.push(AocError::parse(
+                line_number,
+                1,
+                format!(
+                    "expected one item type in both compartments, found {}",
+                    describe(&shared, &priorities, "item types")
+                ),
+            ));
+        }
+        rucksacks.push(Some(rucksack));
+    }
+    if rucksacks.is_empty() {
+        violations.push(AocError::invalid_data("there are no rucksacks"));
+        return violations;
     }
 
-    fn badge_intersection(&self) -> Option<char> {
-        for c in self.rucksacks.0.to_string().chars() {
-            if self.rucksacks.1.to_string().contains(c) && self.rucksacks.2.to_string().contains(c)
-            {
-                return Some(c);
+    match group_ranges(rucksacks.len(), grouping) {
+        Ok(ranges) => {
+            for range in ranges {
+                let Some(group) = rucksacks[range.clone()].iter().cloned().collect() else {
+                    continue;
+                };
+                let badges = RucksackGroup::new(group).badge_items();
+                if badges.len() != 1 {
+                    let (first, last) = (line_numbers[range.start], line_numbers[range.end - 1]);
+                    violations.push(AocError::parse(
+                        first,
+                        1,
+                        format!(
+                            "expected one badge for the group of lines {} to {}, found {}",
+                            first,
+                            last,
+                            describe(&badges, &priorities, "badges")
+                        ),
+                    ));
+                }
             }
         }
-        return None;
+        Err(err) => violations.push(err),
     }
+    violations
 }
 
-pub fn read_input() -> String {
-    return fs::read_to_string("src/day3/input.txt")
-        .expect("Something went wrong reading the file");
+/// Adds up the priorities of `numbers`, leaving out the `None`s.
+fn total_priority(
+    numbers: impl Iterator<Item = Option<usize>>,
+    priorities: &Priorities,
+) -> Result<u64> {
+    let mut score: u64 = 0;
+    for number in numbers.flatten() {
+        score = score
+            .checked_add(priorities.priority(number))
+            .ok_or_else(|| AocError::overflow("the total priority"))?;
+    }
+    Ok(score)
 }
 
-fn parse_input(input: String) -> Vec<Rucksack> {
-    let mut rucksacks: Vec<Rucksack> = Vec::new();
-    for line in input.lines() {
-        let (first_compartment, second_compartment) = line.split_at(line.len() / 2);
-        let rucksack = Rucksack::new(
-            first_compartment.to_string(),
-            second_compartment.to_string(),
-        );
-        rucksacks.push(rucksack);
+pub fn calculate_total_rucksacks_score(
+    rucksacks: &[Rucksack],
+    priorities: &Priorities,
+) -> Result<u64> {
+    total_priority(
+        rucksacks.iter().map(Rucksack::compartment_intersection),
+        priorities,
+    )
+}
+
+pub fn calculate_total_group_score(
+    rucksack_groups: &[RucksackGroup],
+    priorities: &Priorities,
+) -> Result<u64> {
+    total_priority(
+        rucksack_groups
+            .iter()
+            .map(RucksackGroup::badge_intersection),
+        priorities,
+    )
+}
+
+/// The positions of the rucksacks in each group, out of `count`.
+fn group_ranges(count: usize, grouping: &Grouping) -> Result<Vec<Range<usize>>> {
+    let size = match *grouping {
+        Grouping::Chunks { size, .. } | Grouping::Windows { size } => size,
+    };
+    if size == 0 {
+        return Err(AocError::invalid_data("groups need at least one rucksack"));
     }
-    rucksacks
+    let ranges = match *grouping {
+        Grouping::Chunks { remainder, .. } => {
+            let left_over = count % size;
+            let end = match remainder {
+                Remainder::Reject if left_over != 0 => {
+                    return Err(AocError::invalid_data(format!(
+                        "{} rucksacks cannot be split into groups of {}, {} would be left over",
+                        count, size, left_over
+                    )));
+                }
+                Remainder::Drop => count - left_over,
+                Remainder::Reject | Remainder::Keep => count,
+            };
+            (0..end)
+                .step_by(size)
+                .map(|start| start..(start + size).min(end))
+                .collect()
+        }
+        Grouping::Windows { .. } => (size..=count).map(|end| end - size..end).collect(),
+    };
+    Ok(ranges)
 }
 
-fn calculate_total_rucksacks_score(rucksacks: Vec<Rucksack>) -> i32 {
-    let mut score = 0;
-    for rucksack in rucksacks {
-        score = match rucksack.compartment_intersection() {
-            Some(c) => score + (PRIORITIES.find(c).unwrap() as i32 + 1),
-            None => score,
-        };
+pub fn group_rucksacks(rucksacks: &[Rucksack], grouping: &Grouping) -> Result<Vec<RucksackGroup>> {
+    Ok(group_ranges(rucksacks.len(), grouping)?
+        .into_iter()
+        .map(|range| RucksackGroup::new(rucksacks[range].to_vec()))
+        .collect())
+}
+
+/// Groups the rucksacks by three, as the puzzle asks.
+pub fn create_rucksack_groups(rucksacks: &[Rucksack]) -> Result<Vec<RucksackGroup>> {
+    group_rucksacks(rucksacks, &Grouping::default())
+}
+
+fn puzzle_1(inventory: &Inventory) -> Result<u64> {
+    calculate_total_rucksacks_score(&inventory.rucksacks, &inventory.priorities)
+}
+
+fn puzzle_2(inventory: &Inventory, grouping: &Grouping) -> Result<u64> {
+    let rucksack_groups = group_rucksacks(&inventory.rucksacks, grouping)?;
+    calculate_total_group_score(&rucksack_groups, &inventory.priorities)
+}
+
+// --- Day 3: Rucksack Reorganization ---
+//
+// One Elf has the important job of loading all of the rucksacks with supplies for the jungle journey.
+// Unfortunately, that Elf didn't quite follow the packing instructions, and so a few items now need to be rearranged.
+//
+// Each rucksack has two large compartments. All items of a given type are meant to go into exactly one of the two compartments.
+// The Elf that did the packing failed to follow this rule for exactly one item type per rucksack.
+//
+// The Elves have made a list of all of the items currently in each rucksack (your puzzle input), but they need your help finding the errors.
+// Every item type is identified by a single lowercase or uppercase letter (that is, a and A refer to different types of items).
+//
+// The list of items for each rucksack is given as characters all on a single line.
+// A given rucksack always has the same number of items in each of its two compartments, so the first half of the characters represent items in the first compartment,
+// while the second half of the characters represent items in the second compartment.
+//
+// For example, suppose you have the following list of contents from six rucksacks:
+//
+// vJrwpWtwJgWrhcsFMMfFFhFp
+// jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+// PmmdzqPrVvPwwTWBwg
+// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+// ttgJtRGJQctTZtZT
+// CrZsJsPPZsGzwwsLwLmpwMDw
+//
+//     The first rucksack contains the items vJrwpWtwJgWrhcsFMMfFFhFp, which means its first compartment contains the items vJrwpWtwJgWr,
+//     while the second compartment contains the items hcsFMMfFFhFp. The only item type that appears in both compartments is lowercase p.
+//     The second rucksack's compartments contain jqHRNqRjqzjGDLGL and rsFMfFZSrLrFZsSL. The only item type that appears in both compartments is uppercase L.
+//     The third rucksack's compartments contain PmmdzqPrV and vPwwTWBwg; the only common item type is uppercase P.
+//     The fourth rucksack's compartments only share item type v.
+//     The fifth rucksack's compartments only share item type t.
+//     The sixth rucksack's compartments only share item type s.
+//
+// To help prioritize item rearrangement, every item type can be converted to a priority:
+//
+//     Lowercase item types a through z have priorities 1 through 26.
+//     Uppercase item types A through Z have priorities 27 through 52.
+//
+// In the above example, the priority of the item type that appears in both compartments of each rucksack is 16 (p), 38 (L), 42 (P), 22 (v), 20 (t), and 19 (s); the sum of these is 157.
+//
+// Find the item type that appears in both compartments of each rucksack. What is the sum of the priorities of those item types?
+//
+// --- Part Two ---
+//
+// As you finish identifying the misplaced items, the Elves come to you with another issue.
+//
+// For safety, the Elves are divided into groups of three. Every Elf carries a badge that identifies their group.
+// For efficiency, within each group of three Elves, the badge is the only item type carried by all three Elves.
+// That is, if a group's badge is item type B, then all three Elves will have item type B somewhere in their rucksack, and at most two of the Elves will be carrying any other item type.
+//
+// The problem is that someone forgot to put this year's updated authenticity sticker on the badges.
+// All of the badges need to be pulled out of the rucksacks so the new authenticity stickers can be attached.
+//
+// Additionally, nobody wrote down which item type corresponds to each group's badges.
+// The only way to tell which item type is the right one is by finding the one item type that is common between all three Elves in each group.
+//
+// Every set of three lines in your list corresponds to a single group, but each group can have a different badge item type.
+// So, in the above example, the first group's rucksacks are the first three lines:
+//
+// vJrwpWtwJgWrhcsFMMfFFhFp
+// jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+// PmmdzqPrVvPwwTWBwg
+//
+// And the second group's rucksacks are the next three lines:
+//
+// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+// ttgJtRGJQctTZtZT
+// CrZsJsPPZsGzwwsLwLmpwMDw
+//
+// In the first group, the only item type that appears in all three rucksacks is lowercase r; this must be their badges.
+// In the second group, their badge item type must be Z.
+//
+// Priorities for these items must still be found to organize the sticker attachment efforts:
+// here, they are 18 (r) for the first group and 52 (Z) for the second group. The sum of these is 70.
+//
+// Find the item type that corresponds to the badges of each three-Elf group. What is the sum of the priorities of those item types?
+#[derive(Default)]
+pub struct Day3 {
+    /// How part 2 groups the rucksacks.
+    pub grouping: Grouping,
+    /// The item types and their priorities.
+    pub priorities: Priorities,
+}
+
+impl Solution for Day3 {
+    type Model = Inventory;
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn parse(&self, input: &str) -> Result<Inventory> {
+        parse_inventory(input, &self.priorities)
+    }
+
+    fn validate(&self, input: &str) -> Vec<AocError> {
+        validate(input, &self.priorities, &self.grouping)
+    }
+
+    fn part1(&self, inventory: &Inventory) -> Result<String> {
+        Ok(puzzle_1(inventory)?.to_string())
+    }
+
+    fn part2(&self, inventory: &Inventory) -> Result<String> {
+        Ok(puzzle_2(inventory, &self.grouping)?.to_string())
     }
-    score
 }
 
-fn calculate_total_group_score(rucksack_groups: Vec<RucksackGroup>) -> i32 {
-    let mut score = 0;
-    for rucksack_group in rucksack_groups {
-        score = match rucksack_group.badge_intersection() {
-            Some(c) => score + (PRIORITIES.find(c).unwrap() as i32 + 1),
-            None => score,
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::normalize;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_input_splits_compartments_in_half() {
+        let rucksacks = parse_input(EXAMPLE).unwrap().rucksacks;
+        assert_eq!(rucksacks.len(), 6);
+        assert_eq!(rucksacks[0].first_compartment, "vJrwpWtwJgWr");
+        assert_eq!(rucksacks[0].second_compartment, "hcsFMMfFFhFp");
+    }
+
+    #[test]
+    fn compartment_intersection_example() {
+        let inventory = parse_input(EXAMPLE).unwrap();
+        let shared: Vec<Option<char>> = inventory
+            .rucksacks
+            .iter()
+            .map(|rucksack| {
+                rucksack
+                    .compartment_intersection()
+                    .map(|number| inventory.priorities.item(number))
+            })
+            .collect();
+        let expected = ['p', 'L', 'P', 'v', 't', 's'].map(Some);
+        assert_eq!(shared, expected);
+    }
+
+    fn badges(inventory: &Inventory, grouping: Grouping) -> Vec<Option<char>> {
+        group_rucksacks(&inventory.rucksacks, &grouping)
+            .unwrap()
+            .iter()
+            .map(|group| {
+                group
+                    .badge_intersection()
+                    .map(|number| inventory.priorities.item(number))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn badge_intersection_example() {
+        let inventory = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            badges(&inventory, Grouping::default()),
+            vec![Some('r'), Some('Z')]
+        );
+    }
+
+    #[test]
+    fn puzzle_1_example() {
+        assert_eq!(puzzle_1(&parse_input(EXAMPLE).unwrap()).unwrap(), 157);
+    }
+
+    #[test]
+    fn parse_input_reports_unknown_item() {
+        let err = parse_input("abcd\nab1d\n").unwrap_err();
+        assert!(
+            matches!(
+                err,
+                AocError::UnknownItem {
+                    line: 2,
+                    column: 3,
+                    item: '1'
+                }
+            ),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_inventory_uses_the_priority_scheme() {
+        let input = "a1b2\né€ßé\n";
+        assert!(parse_inventory(input, &Priorities::alphanumeric()).is_err());
+
+        let mut priorities = Priorities::alphanumeric();
+        priorities.set('€', 1000);
+        let err = parse_inventory(input, &priorities).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: unknown item `é` (U+00E9)"
+        );
+
+        // Part 1 scores nothing for the first rucksack and `é` for the second.
+        let inventory = parse_inventory(input, &Priorities::unicode()).unwrap();
+        assert_eq!(puzzle_1(&inventory).unwrap(), 0xE9);
+        assert_eq!(inventory.priorities.len(), 7);
+    }
+
+    #[test]
+    fn puzzle_2_example() {
+        assert_eq!(
+            puzzle_2(&parse_input(EXAMPLE).unwrap(), &Grouping::default()).unwrap(),
+            70
+        );
+    }
+
+    #[test]
+    fn create_rucksack_groups_rejects_incomplete_group() {
+        let rucksacks = parse_input("abab\ncdcd\n").unwrap().rucksacks;
+        assert!(matches!(
+            create_rucksack_groups(&rucksacks),
+            Err(AocError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn group_rucksacks_handles_any_size() {
+        let inventory = parse_input(EXAMPLE).unwrap();
+        let badges = |grouping| badges(&inventory, grouping);
+        let chunks = |size, remainder| Grouping::Chunks { size, remainder };
+
+        assert_eq!(badges(chunks(6, Remainder::Reject)), vec![None]);
+        assert_eq!(
+            badges(chunks(4, Remainder::Drop)),
+            vec![None],
+            "the last two rucksacks are dropped"
+        );
+        assert_eq!(badges(chunks(4, Remainder::Keep)), vec![None, Some('G')]);
+        let rucksacks = &inventory.rucksacks;
+        assert!(group_rucksacks(rucksacks, &chunks(4, Remainder::Reject)).is_err());
+        assert!(group_rucksacks(rucksacks, &chunks(0, Remainder::Keep)).is_err());
+
+        // Neighbouring rucksacks share more than one item; the lowest priority wins.
+        assert_eq!(
+            badges(Grouping::Windows { size: 2 }),
+            ['f', 'q', 'q', 'c', 'G'].map(Some)
+        );
+        assert!(badges(Grouping::Windows { size: 7 }).is_empty());
+    }
+
+    #[test]
+    fn parse_inventory_skips_blank_lines_left_by_normalization() {
+        let input = EXAMPLE
+            .replace('\n', "\r\n")
+            .replacen("\r\n", "\r\n\r\n", 3);
+        let normalized = normalize(&format!("\u{feff}{}", input));
+        let inventory = parse_input(normalized.text()).unwrap();
+        assert_eq!(inventory.rucksacks.len(), 6);
+        assert_eq!(puzzle_1(&inventory).unwrap(), 157);
+        assert_eq!(puzzle_2(&inventory, &Grouping::default()).unwrap(), 70);
+        assert!(Day3::default().validate(normalized.text()).is_empty());
+    }
+
+    #[test]
+    fn validate_reports_every_broken_guarantee() {
+        let day3 = Day3::default();
+        assert!(day3.validate(EXAMPLE).is_empty());
+
+        let input = "abca\nabcdb\nab1d\n\nabcd\nadbc\nxyzx\n";
+        let violations: Vec<String> = day3
+            .validate(input)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "line 2, column 1: 5 items cannot be split evenly between two compartments",
+                "line 3, column 3: unknown item `1` (U+0031)",
+                "line 5, column 1: expected one item type in both compartments, found no item types",
+                "line 6, column 1: expected one item type in both compartments, found no item types",
+                "line 5, column 1: expected one badge for the group of lines 5 to 7, found no badges",
+            ]
+        );
+
+        let grouping = Grouping::Windows { size: 2 };
+        let violations = validate("abca\nabca\n", &Priorities::letters(), &grouping);
+        assert_eq!(
+            violations[0].to_string(),
+            "line 1, column 1: expected one badge for the group of lines 1 to 2, found 3 badges, `abc`"
+        );
+
+        let violations = day3.validate("\n");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].to_string(),
+            "invalid puzzle data: there are no rucksacks"
+        );
     }
-    score
-}
-
-fn create_rucksack_groups(rucksacks: Vec<Rucksack>) -> Vec<RucksackGroup> {
-    let mut rucksack_groups: Vec<RucksackGroup> = Vec::new();
-    for rucksacks in rucksacks.chunks(3) {
-        rucksack_groups.push(RucksackGroup::new({
-            (
-                rucksacks[0].clone(),
-                rucksacks[1].clone(),
-                rucksacks[2].clone(),
-            )
-        }));
-    }
-
-    rucksack_groups
-}
-
-fn puzzle_1() {
-    let input = read_input();
-    let rucksacks: Vec<Rucksack> = parse_input(input);
-    let score = calculate_total_rucksacks_score(rucksacks);
-    println!("Puzzle 1 score: {}", score);
-    // Puzzle 1 score: 7831
-}
-
-fn puzzle_2() {
-    let input = read_input();
-    let rucksacks: Vec<Rucksack> = parse_input(input);
-    let rucksack_groups = create_rucksack_groups(rucksacks);
-    let score = calculate_total_group_score(rucksack_groups);
-    println!("Puzzle 2 score: {}", score);
-    // Puzzle 2 score: 2683
-}
-
-pub(crate) fn run() {
-    // --- Day 3: Rucksack Reorganization ---
-    //
-    // One Elf has the important job of loading all of the rucksacks with supplies for the jungle journey.
-    // Unfortunately, that Elf didn't quite follow the packing instructions, and so a few items now need to be rearranged.
-    //
-    // Each rucksack has two large compartments. All items of a given type are meant to go into exactly one of the two compartments.
-    // The Elf that did the packing failed to follow this rule for exactly one item type per rucksack.
-    //
-    // The Elves have made a list of all of the items currently in each rucksack (your puzzle input), but they need your help finding the errors.
-    // Every item type is identified by a single lowercase or uppercase letter (that is, a and A refer to different types of items).
-    //
-    // The list of items for each rucksack is given as characters all on a single line.
-    // A given rucksack always has the same number of items in each of its two compartments, so the first half of the characters represent items in the first compartment,
-    // while the second half of the characters represent items in the second compartment.
-    //
-    // For example, suppose you have the following list of contents from six rucksacks:
-    //
-    // vJrwpWtwJgWrhcsFMMfFFhFp
-    // jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-    // PmmdzqPrVvPwwTWBwg
-    // wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-    // ttgJtRGJQctTZtZT
-    // CrZsJsPPZsGzwwsLwLmpwMDw
-    //
-    //     The first rucksack contains the items vJrwpWtwJgWrhcsFMMfFFhFp, which means its first compartment contains the items vJrwpWtwJgWr,
-    //     while the second compartment contains the items hcsFMMfFFhFp. The only item type that appears in both compartments is lowercase p.
-    //     The second rucksack's compartments contain jqHRNqRjqzjGDLGL and rsFMfFZSrLrFZsSL. The only item type that appears in both compartments is uppercase L.
-    //     The third rucksack's compartments contain PmmdzqPrV and vPwwTWBwg; the only common item type is uppercase P.
-    //     The fourth rucksack's compartments only share item type v.
-    //     The fifth rucksack's compartments only share item type t.
-    //     The sixth rucksack's compartments only share item type s.
-    //
-    // To help prioritize item rearrangement, every item type can be converted to a priority:
-    //
-    //     Lowercase item types a through z have priorities 1 through 26.
-    //     Uppercase item types A through Z have priorities 27 through 52.
-    //
-    // In the above example, the priority of the item type that appears in both compartments of each rucksack is 16 (p), 38 (L), 42 (P), 22 (v), 20 (t), and 19 (s); the sum of these is 157.
-    //
-    // Find the item type that appears in both compartments of each rucksack. What is the sum of the priorities of those item types?
-    //
-    // --- Part Two ---
-    //
-    // As you finish identifying the misplaced items, the Elves come to you with another issue.
-    //
-    // For safety, the Elves are divided into groups of three. Every Elf carries a badge that identifies their group.
-    // For efficiency, within each group of three Elves, the badge is the only item type carried by all three Elves.
-    // That is, if a group's badge is item type B, then all three Elves will have item type B somewhere in their rucksack, and at most two of the Elves will be carrying any other item type.
-    //
-    // The problem is that someone forgot to put this year's updated authenticity sticker on the badges.
-    // All of the badges need to be pulled out of the rucksacks so the new authenticity stickers can be attached.
-    //
-    // Additionally, nobody wrote down which item type corresponds to each group's badges.
-    // The only way to tell which item type is the right one is by finding the one item type that is common between all three Elves in each group.
-    //
-    // Every set of three lines in your list corresponds to a single group, but each group can have a different badge item type.
-    // So, in the above example, the first group's rucksacks are the first three lines:
-    //
-    // vJrwpWtwJgWrhcsFMMfFFhFp
-    // jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-    // PmmdzqPrVvPwwTWBwg
-    //
-    // And the second group's rucksacks are the next three lines:
-    //
-    // wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-    // ttgJtRGJQctTZtZT
-    // CrZsJsPPZsGzwwsLwLmpwMDw
-    //
-    // In the first group, the only item type that appears in all three rucksacks is lowercase r; this must be their badges.
-    // In the second group, their badge item type must be Z.
-    //
-    // Priorities for these items must still be found to organize the sticker attachment efforts:
-    // here, they are 18 (r) for the first group and 52 (Z) for the second group. The sum of these is 70.
-    //
-    // Find the item type that corresponds to the badges of each three-Elf group. What is the sum of the priorities of those item types?
-    //
-    puzzle_1();
-    puzzle_2();
 }
@@ -0,0 +1,296 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::error::{AocError, Result};
+
+/// Marks an ASCII character that is not an item in [`Priorities::ascii`].
+const NOT_AN_ITEM: u32 = u32::MAX;
+
+/// The item types of an inventory and the priority of each. Items are
+/// numbered in the order they were declared, which is the order
+/// [`ItemSet`](super::item_set::ItemSet)s list them in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Priorities {
+    /// The items by number, with their priority.
+    items: Vec<(char, u64)>,
+    /// The number of each non-ASCII item.
+    numbers: HashMap<char, usize>,
+    /// The number of each ASCII item, looked up without hashing.
+    ascii: [u32; 128],
+    /// Whether any character is an item, numbered when first met and with
+    /// its code point as priority.
+    open: bool,
+}
+
+impl Default for Priorities {
+    fn default() -> Priorities {
+        Priorities::letters()
+    }
+}
+
+impl Priorities {
+    /// The names [`Priorities::builtin`] knows.
+    pub const BUILTINS: [&'static str; 4] = ["letters", "alphanumeric", "ascii", "unicode"];
+
+    fn empty() -> Priorities {
+        Priorities {
+            items: Vec::new(),
+            numbers: HashMap::new(),
+            ascii: [NOT_AN_ITEM; 128],
+            open: false,
+        }
+    }
+
+    /// `items` in order, with priorities 1, 2, 3...
+    fn ranked(items: impl IntoIterator<Item = char>) -> Priorities {
+        let mut priorities = Priorities::empty();
+        for (item, priority) in items.into_iter().zip(1..) {
+            priorities.set(item, priority);
+        }
+        priorities
+    }
+
+    /// The puzzle's scheme: 1 through 26 for `a` through `z`, then 27
+    /// through 52 for `A` through `Z`.
+    pub fn letters() -> Priorities {
+        Priorities::ranked(('a'..='z').chain('A'..='Z'))
+    }
+
+    /// The letters, followed by the digits `0` through `9` at 53 through 62.
+    pub fn alphanumeric() -> Priorities {
+        Priorities::ranked(('a'..='z').chain('A'..='Z').chain('0'..='9'))
+    }
+
+    /// Every printable ASCII character but the space: the letters and
+    /// digits as in [`Priorities::alphanumeric`], then the punctuation in
+    /// code point order.
+    pub fn ascii() -> Priorities {
+        let punctuation = ('!'..='~').filter(|c| !c.is_ascii_alphanumeric());
+        Priorities::ranked(
+            ('a'..='z')
+                .chain('A'..='Z')
+                .chain('0'..='9')
+                .chain(punctuation),
+        )
+    }
+
+    /// Any character is an item, with its code point as priority.
+    pub fn unicode() -> Priorities {
+        Priorities {
+            open: true,
+            ..Priorities::empty()
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Priorities> {
+        match name {
+            "letters" => Some(Priorities::letters()),
+            "alphanumeric" => Some(Priorities::alphanumeric()),
+            "ascii" => Some(Priorities::ascii()),
+            "unicode" => Some(Priorities::unicode()),
+            _ => None,
+        }
+    }
+
+    /// A built-in scheme by name, or else the scheme in the file at `arg`.
+    pub fn from_arg(arg: &str) -> Result<Priorities> {
+        match Priorities::builtin(arg) {
+            Some(priorities) => Ok(priorities),
+            None => Priorities::load(Path::new(arg)),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Priorities> {
+        let content = fs::read_to_string(path).map_err(|source| AocError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Priorities::parse(&content)
+    }
+
+    /// Reads a scheme with one `ITEM PRIORITY` pair per line. A first
+    /// `extends NAME` line starts from a built-in scheme, whose priorities
+    /// later lines may change; otherwise only the listed items exist.
+    /// Items are single characters, or `U+XXXX` for any code point
+    /// including `#` and whitespace. Lines starting with `#` are comments:
+    ///
+    /// ```text
+    /// extends letters
+    /// é 53
+    /// U+00DF 54
+    /// ```
+    pub fn parse(content: &str) -> Result<Priorities> {
+        let mut priorities = Priorities::empty();
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        if let Some(&(line_number, line)) = lines.peek() {
+            if let Some(name) = line.strip_prefix("extends ") {
+                priorities = Priorities::builtin(name.trim()).ok_or_else(|| {
+                    AocError::parse(
+                        line_number,
+                        "extends ".len() + 1,
+                        format!(
+                            "unknown priority scheme `{}`, expected one of {}",
+                            name.trim(),
+                            Priorities::BUILTINS.join(", ")
+                        ),
+                    )
+                })?;
+                lines.next();
+            }
+        }
+
+        for (line_number, line) in lines {
+            let (item, priority) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| AocError::parse(line_number, 1, "expected `ITEM PRIORITY`"))?;
+            let item = parse_item(item)
+                .ok_or_else(|| AocError::parse(line_number, 1, format!("bad item `{}`", item)))?;
+            let priority = priority.trim();
+            let priority = priority.parse::<u64>().map_err(|_| {
+                AocError::parse(
+                    line_number,
+                    line.len() - priority.len() + 1,
+                    format!("bad priority `{}`", priority),
+                )
+            })?;
+            priorities.set(item, priority);
+        }
+        Ok(priorities)
+    }
+
+    /// Adds `item`, or changes its priority if it is already one.
+    pub fn set(&mut self, item: char, priority: u64) {
+        match self.number(item) {
+            Some(number) => self.items[number].1 = priority,
+            None => self.push(item, priority),
+        }
+    }
+
+    fn push(&mut self, item: char, priority: u64) {
+        let number = self.items.len();
+        self.items.push((item, priority));
+        match u8::try_from(item) {
+            Ok(byte) if byte.is_ascii() => self.ascii[byte as usize] = number as u32,
+            _ => {
+                self.numbers.insert(item, number);
+            }
+        }
+    }
+
+    /// The number of `item`, if it is a known item.
+    pub fn number(&self, item: char) -> Option<usize> {
+        match u8::try_from(item) {
+            Ok(byte) if byte.is_ascii() => match self.ascii[byte as usize] {
+                NOT_AN_ITEM => None,
+                number => Some(number as usize),
+            },
+            _ => self.numbers.get(&item).copied(),
+        }
+    }
+
+    /// The number of `item`, numbering it first if the scheme takes any
+    /// character. `None` when `item` is not an item.
+    pub fn intern(&mut self, item: char) -> Option<usize> {
+        match self.number(item) {
+            Some(number) => Some(number),
+            None if self.open => {
+                self.push(item, item as u64);
+                Some(self.items.len() - 1)
+            }
+            None => None,
+        }
+    }
+
+    /// Whether `item` is an item of the scheme.
+    pub fn accepts(&self, item: char) -> bool {
+        self.open || self.number(item).is_some()
+    }
+
+    pub fn item(&self, number: usize) -> char {
+        self.items[number].0
+    }
+
+    pub fn priority(&self, number: usize) -> u64 {
+        self.items[number].1
+    }
+
+    /// How many items have been numbered so far.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// A single character, or a `U+XXXX` code point.
+fn parse_item(text: &str) -> Option<char> {
+    if let Some(hex) = text.strip_prefix("U+") {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(item), None) => Some(item),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtins_extend_the_letters() {
+        let letters = Priorities::letters();
+        let priority = |priorities: &Priorities, item| {
+            priorities
+                .number(item)
+                .map(|number| priorities.priority(number))
+        };
+        assert_eq!(priority(&letters, 'a'), Some(1));
+        assert_eq!(priority(&letters, 'Z'), Some(52));
+        assert_eq!(priority(&letters, '1'), None);
+        assert_eq!(priority(&Priorities::alphanumeric(), '9'), Some(62));
+        let ascii = Priorities::ascii();
+        assert_eq!(ascii.len(), 94);
+        assert_eq!(priority(&ascii, '!'), Some(63));
+        assert!(!ascii.accepts(' '));
+
+        let mut unicode = Priorities::unicode();
+        assert!(unicode.is_empty() && unicode.accepts('é'));
+        let number = unicode.intern('é').unwrap();
+        assert_eq!(unicode.priority(number), 0xE9);
+        assert_eq!(unicode.intern('é'), Some(number));
+        assert_eq!(letters.clone().intern('é'), None);
+    }
+
+    #[test]
+    fn parse_reads_custom_weights() {
+        let priorities = Priorities::parse(
+            "# Rucksacks from the north\nextends letters\n\né 53\nU+0023 54\na 100\n",
+        )
+        .unwrap();
+        assert_eq!(priorities.len(), 54);
+        assert_eq!(priorities.priority(priorities.number('é').unwrap()), 53);
+        assert_eq!(priorities.priority(priorities.number('#').unwrap()), 54);
+        assert_eq!(priorities.priority(priorities.number('a').unwrap()), 100);
+
+        let only = Priorities::parse("x 5\ny 7\n").unwrap();
+        assert_eq!((only.len(), only.number('a')), (2, None));
+    }
+
+    #[test]
+    fn parse_points_at_mistakes() {
+        let err = Priorities::parse("extends letters\nab 3\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: bad item `ab`");
+        let err = Priorities::parse("a  three\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 4: bad priority `three`");
+        assert!(Priorities::parse("extends greek\n").is_err());
+    }
+}
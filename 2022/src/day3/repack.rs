@@ -0,0 +1,235 @@
+use std::fmt;
+
+use crate::day3::{priorities::Priorities, Inventory, Rucksack};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compartment {
+    First,
+    Second,
+}
+
+impl fmt::Display for Compartment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Compartment::First => f.pad("first"),
+            Compartment::Second => f.pad("second"),
+        }
+    }
+}
+
+/// Moving `count` items of one type into the `to` compartment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub item: char,
+    pub count: usize,
+    pub to: Compartment,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} to {}", self.count, self.item, self.to)
+    }
+}
+
+/// How to repack one rucksack so that no item type is in both of its
+/// compartments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// The position of the rucksack in the list, from 1.
+    pub rucksack: usize,
+    /// Every item type found in both compartments, in priority scheme order.
+    pub shared: Vec<char>,
+    /// The fewest moves that make the compartments disjoint.
+    pub moves: Vec<Move>,
+    /// The size of both compartments after the moves.
+    pub sizes: (usize, usize),
+    /// Whether the compartments keep the sizes they had. When no repacking
+    /// can, the plan leaves them as close to that as possible.
+    pub balanced: bool,
+}
+
+impl Plan {
+    /// How many items the plan moves.
+    pub fn moved(&self) -> usize {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+}
+
+/// How many items of one type each compartment holds.
+struct Count {
+    item: char,
+    first: usize,
+    second: usize,
+}
+
+/// Counts each item type of `rucksack` in both compartments, in scheme order.
+fn counts(rucksack: &Rucksack, priorities: &Priorities) -> Vec<Count> {
+    let (first, second) = rucksack.compartments();
+    rucksack
+        .items()
+        .iter()
+        .map(|number| {
+            let item = priorities.item(number);
+            Count {
+                item,
+                first: first.chars().filter(|&c| c == item).count(),
+                second: second.chars().filter(|&c| c == item).count(),
+            }
+        })
+        .collect()
+}
+
+/// Plans the repacking of `rucksack`. Each item type ends up wholly in one
+/// compartment, costing the items of it that have to move there. Choosing
+/// the sides is a knapsack over the size of the first compartment: of the
+/// sizes closest to the current one, the plan takes the cheapest.
+pub fn plan(index: usize, rucksack: &Rucksack, priorities: &Priorities) -> Plan {
+    let counts = counts(rucksack, priorities);
+    let (first, second) = rucksack.compartments();
+    let target = first.chars().count();
+    let total = target + second.chars().count();
+
+    // `cost[s]` is the fewest moves that leave `s` items in the first
+    // compartment, and `in_first[t][s]` whether type `t` stays there for it.
+    let mut cost: Vec<Option<usize>> = vec![None; total + 1];
+    cost[0] = Some(0);
+    let mut in_first = Vec::with_capacity(counts.len());
+    for count in &counts {
+        let size = count.first + count.second;
+        let mut next = vec![None; total + 1];
+        let mut chosen = vec![false; total + 1];
+        for (s, moves) in cost.iter().enumerate() {
+            let Some(moves) = *moves else { continue };
+            let options = [
+                (s + size, moves + count.second, true),
+                (s, moves + count.first, false),
+            ];
+            for (s, moves, first) in options {
+                if next[s].is_none_or(|best| moves < best) {
+                    next[s] = Some(moves);
+                    chosen[s] = first;
+                }
+            }
+        }
+        cost = next;
+        in_first.push(chosen);
+    }
+
+    let size = (0..=total)
+        .filter(|&s| cost[s].is_some())
+        .min_by_key(|&s| (s.abs_diff(target), cost[s]))
+        .expect("some packing always exists");
+    let mut moves = Vec::new();
+    let mut s = size;
+    for (count, chosen) in counts.iter().zip(&in_first).rev() {
+        if chosen[s] {
+            s -= count.first + count.second;
+            if count.second > 0 {
+                moves.push(Move {
+                    item: count.item,
+                    count: count.second,
+                    to: Compartment::First,
+                });
+            }
+        } else if count.first > 0 {
+            moves.push(Move {
+                item: count.item,
+                count: count.first,
+                to: Compartment::Second,
+            });
+        }
+    }
+    moves.reverse();
+
+    Plan {
+        rucksack: index + 1,
+        shared: rucksack
+            .shared_items()
+            .iter()
+            .map(|number| priorities.item(number))
+            .collect(),
+        moves,
+        sizes: (size, total - size),
+        balanced: size == target,
+    }
+}
+
+/// Plans every rucksack of `inventory`, in order.
+pub fn plan_all(inventory: &Inventory) -> Vec<Plan> {
+    inventory
+        .rucksacks
+        .iter()
+        .enumerate()
+        .map(|(index, rucksack)| plan(index, rucksack, &inventory.priorities))
+        .collect()
+}
+
+/// Prints the plans of the rucksacks that need repacking, then a summary.
+pub fn print_plans(plans: &[Plan]) {
+    println!("{:>8}  {:<12}  Moves", "Rucksack", "Shared");
+    for plan in plans.iter().filter(|plan| !plan.moves.is_empty()) {
+        let moves: Vec<String> = plan.moves.iter().map(Move::to_string).collect();
+        let shared: String = plan.shared.iter().collect();
+        print!("{:>8}  {:<12}  {}", plan.rucksack, shared, moves.join(", "));
+        if !plan.balanced {
+            print!(" (leaves {} and {})", plan.sizes.0, plan.sizes.1);
+        }
+        println!();
+    }
+    let repacked = plans.iter().filter(|plan| !plan.moves.is_empty()).count();
+    let moved: usize = plans.iter().map(Plan::moved).sum();
+    println!(
+        "\n{} of {} rucksacks need repacking, moving {} items",
+        repacked,
+        plans.len(),
+        moved
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day3::parse_input;
+
+    fn plans(input: &str) -> Vec<Plan> {
+        plan_all(&parse_input(input).unwrap())
+    }
+
+    #[test]
+    fn plan_swaps_to_keep_compartments_balanced() {
+        let plans = plans("vJrwpWtwJgWrhcsFMMfFFhFp\nabcbda\n");
+        // Moving a `p` across takes another item back to keep twelve a side.
+        let plan = &plans[0];
+        assert_eq!(plan.shared, vec!['p']);
+        assert!(plan.balanced);
+        assert_eq!(plan.moved(), 2);
+        assert_eq!(plan.sizes, (12, 12));
+
+        // `abc|bda`: `b` and `a` are shared.
+        let plan = &plans[1];
+        assert_eq!(plan.shared, vec!['a', 'b']);
+        assert!(plan.balanced);
+        assert_eq!(plan.moved(), 2);
+    }
+
+    #[test]
+    fn plan_reports_when_balance_is_impossible() {
+        let plan = &plans("aaaa\n")[0];
+        assert_eq!(
+            plan.moves,
+            vec![Move {
+                item: 'a',
+                count: 2,
+                to: Compartment::Second
+            }]
+        );
+        assert_eq!((plan.sizes, plan.balanced), ((0, 4), false));
+    }
+
+    #[test]
+    fn plan_leaves_disjoint_rucksacks_alone() {
+        let plans = plans("abcd\n");
+        assert!(plans[0].moves.is_empty() && plans[0].shared.is_empty());
+        assert!(plans[0].balanced);
+    }
+}
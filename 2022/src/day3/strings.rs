@@ -0,0 +1,80 @@
+//! The original string-scanning solution, kept as a baseline for the
+//! [`ItemSet`](super::item_set::ItemSet) one in the benchmarks. It reads the
+//! input itself, as slices of its lines, and only knows the puzzle's letters.
+
+use crate::{
+    error::{AocError, Result},
+    normalize::non_blank_lines,
+};
+
+const PRIORITIES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn compartment_intersection(rucksack: &str) -> Option<char> {
+    let middle = rucksack
+        .char_indices()
+        .nth(rucksack.chars().count() / 2)
+        .map_or(rucksack.len(), |(offset, _)| offset);
+    let (first, second) = rucksack.split_at(middle);
+    first.chars().find(|&c| second.contains(c))
+}
+
+fn badge_intersection(group: &[&str]) -> Option<char> {
+    let (first, others) = group.split_first()?;
+    first
+        .chars()
+        .find(|&c| others.iter().all(|rucksack| rucksack.contains(c)))
+}
+
+fn priority(item: char) -> Result<u64> {
+    PRIORITIES
+        .find(item)
+        .map(|index| index as u64 + 1)
+        .ok_or_else(|| AocError::invalid_data(format!("unknown item `{}`", item)))
+}
+
+fn total(items: impl Iterator<Item = Option<char>>) -> Result<u64> {
+    let mut score: u64 = 0;
+    for item in items.flatten() {
+        score = score
+            .checked_add(priority(item)?)
+            .ok_or_else(|| AocError::overflow("the total priority"))?;
+    }
+    Ok(score)
+}
+
+pub fn calculate_total_rucksacks_score(input: &str) -> Result<u64> {
+    total(non_blank_lines(input).map(|(_, line)| compartment_intersection(line)))
+}
+
+pub fn calculate_total_group_score(input: &str) -> Result<u64> {
+    let rucksacks: Vec<&str> = non_blank_lines(input).map(|(_, line)| line).collect();
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(AocError::invalid_data(format!(
+            "{} rucksacks cannot be split into groups of three",
+            rucksacks.len()
+        )));
+    }
+    total(rucksacks.chunks(3).map(badge_intersection))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day3::{create_rucksack_groups, parse_input};
+
+    #[test]
+    fn baseline_agrees_with_item_sets() {
+        let example = include_str!("example.txt");
+        let inventory = parse_input(example).unwrap();
+        let (rucksacks, priorities) = (&inventory.rucksacks, &inventory.priorities);
+        let groups = create_rucksack_groups(rucksacks).unwrap();
+        assert_eq!(
+            calculate_total_rucksacks_score(example).unwrap(),
+            crate::day3::calculate_total_rucksacks_score(rucksacks, priorities).unwrap()
+        );
+        assert_eq!(
+            calculate_total_group_score(example).unwrap(),
+            crate::day3::calculate_total_group_score(&groups, priorities).unwrap()
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::{fmt, io, path::PathBuf};
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// Reading an input or data file failed.
+    Io { path: PathBuf, source: io::Error },
+    /// The text could not be parsed; `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A character of the input is not an item type of the priority scheme.
+    UnknownItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
+    /// The input parsed but does not describe a valid puzzle.
+    InvalidData(String),
+    /// A total grew past the range of the integer holding it.
+    Overflow(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_data(message: impl Into<String>) -> AocError {
+        AocError::InvalidData(message.into())
+    }
+
+    pub fn overflow(what: impl Into<String>) -> AocError {
+        AocError::Overflow(what.into())
+    }
+}
+
+/// Adds up `values`, failing with [`AocError::Overflow`] naming `what`
+/// instead of wrapping around.
+pub fn checked_sum(what: &str, values: impl IntoIterator<Item = u64>) -> Result<u64> {
+    values.into_iter().try_fold(0u64, |sum, value| {
+        sum.checked_add(value)
+            .ok_or_else(|| AocError::overflow(what))
+    })
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => {
+                write!(f, "could not read `{}`: {}", path.display(), source)
+            }
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::UnknownItem { line, column, item } => write!(
+                f,
+                "line {}, column {}: unknown item `{}` (U+{:04X})",
+                line, column, item, *item as u32
+            ),
+            AocError::InvalidData(message) => write!(f, "invalid puzzle data: {}", message),
+            AocError::Overflow(what) => write!(f, "{} does not fit in 64 bits", what),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
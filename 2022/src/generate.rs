@@ -0,0 +1,96 @@
+use std::fmt::Write;
+
+use crate::{
+    day1::{self, generate::Distribution},
+    day2, day3,
+    error::{AocError, Result},
+    rng::Rng,
+};
+
+/// A generated input and the answers to both of its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: [u64; 2],
+}
+
+/// What to generate, beyond the day and the seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Settings {
+    /// How many elves, rounds or rucksacks; as many as in the checked-in
+    /// input by default.
+    pub size: Option<usize>,
+    /// How the day 1 calories are spread.
+    pub distribution: Distribution,
+}
+
+/// Generates an input for `day`. The same seed and settings always give the
+/// same input.
+pub fn generate(day: u8, seed: u64, settings: &Settings) -> Result<Generated> {
+    let mut rng = Rng::new(seed);
+    match day {
+        1 => day1::generate::generate(
+            &mut rng,
+            settings.size.unwrap_or(day1::generate::DEFAULT_ELVES),
+            settings.distribution,
+        ),
+        2 => Ok(day2::generate::generate(
+            &mut rng,
+            settings.size.unwrap_or(day2::generate::DEFAULT_ROUNDS),
+        )),
+        3 => day3::generate::generate(
+            &mut rng,
+            settings.size.unwrap_or(day3::generate::DEFAULT_RUCKSACKS),
+        ),
+        _ => Err(AocError::invalid_data(format!(
+            "there is no generator for day {}",
+            day
+        ))),
+    }
+}
+
+/// The answers of `generated` in the format of the answers file, so that
+/// `--verify --answers` can check the solvers against them.
+pub fn answers_file(day: u8, seed: u64, generated: &Generated) -> String {
+    let mut content = format!("# Generated for day {} with seed {}.\n", day, seed);
+    for (part, answer) in generated.answers.iter().enumerate() {
+        writeln!(content, "{} {} {}", day, part + 1, answer)
+            .expect("writing to a string cannot fail");
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers::Answers, solution::Part};
+
+    #[test]
+    fn generate_is_reproducible() {
+        let settings = Settings {
+            size: Some(30),
+            ..Settings::default()
+        };
+        for day in 1..=3 {
+            assert_eq!(
+                generate(day, 9, &settings).unwrap(),
+                generate(day, 9, &settings).unwrap()
+            );
+            assert_ne!(
+                generate(day, 9, &settings).unwrap().input,
+                generate(day, 10, &settings).unwrap().input
+            );
+        }
+        assert!(generate(4, 9, &settings).is_err());
+    }
+
+    #[test]
+    fn answers_file_parses_back() {
+        let generated = generate(2, 1, &Settings::default()).unwrap();
+        let answers = Answers::parse(&answers_file(2, 1, &generated)).unwrap();
+        assert_eq!(
+            answers.get(2, Part::Two),
+            Some(generated.answers[1].to_string().as_str())
+        );
+    }
+}
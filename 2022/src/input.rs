@@ -0,0 +1,89 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{AocError, Result},
+    normalize::{self, normalize, Normalized},
+};
+
+/// Directory holding the checked-in inputs, laid out as `day<N>/input.txt`:
+/// the `src` directory of the source tree the binary was built from.
+pub const DEFAULT_INPUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// An explicit input file, used as is for the selected day.
+    File(PathBuf),
+    /// The whole of standard input, selected with `-`.
+    Stdin,
+    /// A directory following the `day<N>/input.txt` convention.
+    Directory(PathBuf),
+}
+
+impl Default for Input {
+    fn default() -> Input {
+        Input::Directory(PathBuf::from(DEFAULT_INPUTS_DIR))
+    }
+}
+
+impl Input {
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        match self {
+            Input::File(path) => path.clone(),
+            Input::Stdin => PathBuf::from("<stdin>"),
+            Input::Directory(dir) => day_path(dir, day),
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<String> {
+        let content = match self {
+            Input::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content).map(|_| content)
+            }
+            _ => fs::read_to_string(self.path(day)),
+        };
+        content.map_err(|source| AocError::Io {
+            path: self.path(day),
+            source,
+        })
+    }
+
+    /// Loads the input and cleans it up with [`normalize`], printing each
+    /// clean-up when `verbose` is set.
+    pub fn load_normalized(&self, day: u8, verbose: bool) -> Result<Normalized> {
+        let normalized = normalize(&self.load(day)?);
+        if verbose {
+            normalize::report(&self.path(day), normalized.applied());
+        }
+        Ok(normalized)
+    }
+
+    /// Opens the input for reading line by line instead of loading it whole.
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>> {
+        match self {
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+            _ => match File::open(self.path(day)) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(source) => Err(AocError::Io {
+                    path: self.path(day),
+                    source,
+                }),
+            },
+        }
+    }
+}
+
+fn day_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}", day)).join("input.txt")
+}
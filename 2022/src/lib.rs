@@ -0,0 +1,14 @@
+pub mod answers;
+pub mod cli;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod error;
+pub mod generate;
+pub mod input;
+pub mod normalize;
+pub mod rng;
+pub mod runner;
+pub mod solution;
+pub mod timing;
+pub mod verify;
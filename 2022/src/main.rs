@@ -1,9 +1,265 @@
-mod day1;
-mod day2;
-mod day3;
+use std::{env, fmt::Display, fs, path::Path, process};
+
+use aoc::{
+    answers::Answers,
+    cli::{self, Command, Format},
+    day1,
+    day2::{
+        self, analysis,
+        cipher::Cipher,
+        crack,
+        tournament::{self, Player},
+        utils::{GuideEntry, Round},
+        Day2,
+    },
+    day3::{self, repack},
+    error::{AocError, Result},
+    generate,
+    input::Input,
+    normalize, runner,
+    solution::{self, Part},
+    timing,
+    verify::{self, Status},
+};
+
+fn exit_with_error(err: impl Display) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
+
+fn load_guide(input: &Input, verbose: bool) -> Result<Vec<GuideEntry>> {
+    let input = input.load_normalized(2, verbose)?;
+    day2::utils::parse_input(input.text()).map_err(|err| input.remap(err))
+}
 
 fn main() {
-    // day1::run();
-    // day2::run();
-    day3::run()
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args, &solution::days()) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Stream {
+            part,
+            input,
+            options,
+        } => {
+            let summary = input
+                .open(1)
+                .and_then(|reader| {
+                    day1::stream::parse_stream(reader, &input.path(1), options.top_n)
+                })
+                .unwrap_or_else(|err| exit_with_error(err));
+            if options.verbose {
+                normalize::report(&input.path(1), &summary.normalizations);
+            }
+            for part in Part::selected(part) {
+                let answer = match part {
+                    Part::One => summary.part1(),
+                    Part::Two => summary.part2(),
+                };
+                match answer {
+                    Ok(answer) => println!("Part {}: {}", part, answer),
+                    Err(err) => exit_with_error(format!("day 1 part {}: {}", part, err)),
+                }
+            }
+        }
+        Command::Stats {
+            input,
+            buckets,
+            format,
+            verbose,
+        } => {
+            let stats = input
+                .load_normalized(1, verbose)
+                .and_then(|input| day1::parse_input(input.text()).map_err(|err| input.remap(err)))
+                .and_then(|elves| day1::stats::calorie_stats(&elves, buckets))
+                .unwrap_or_else(|err| exit_with_error(err));
+            match format {
+                Format::Table => stats.print_table(),
+                Format::Json => print!("{}", stats.to_json()),
+            }
+        }
+        Command::Tournament {
+            guides,
+            part,
+            progression,
+            options,
+        } => {
+            let day2 = Day2::new(options.rules, &options.cipher);
+            let cipher = match part {
+                Part::One => &day2.part1,
+                Part::Two => &day2.part2,
+            };
+            let guides = if guides.is_empty() {
+                vec![Input::default()]
+            } else {
+                guides
+            };
+            let games: Vec<(String, Vec<Round>)> = guides
+                .iter()
+                .map(|input| {
+                    let rounds = load_guide(input, options.verbose)
+                        .and_then(|guide| cipher.decode(&day2.rules, &guide))
+                        .unwrap_or_else(|err| exit_with_error(err));
+                    (input.path(2).display().to_string(), rounds)
+                })
+                .collect();
+
+            if let [(_, rounds)] = &games[..] {
+                let game = tournament::play_guide(&day2.rules, rounds)
+                    .unwrap_or_else(|err| exit_with_error(err));
+                tournament::print_game(&game, ["Me", "Opponent"], progression);
+            } else {
+                let players: Vec<Player> = games
+                    .into_iter()
+                    .map(|(name, rounds)| Player {
+                        name,
+                        moves: rounds.into_iter().map(|round| round.my_shape).collect(),
+                    })
+                    .collect();
+                let round_robin = tournament::round_robin(&day2.rules, &players)
+                    .unwrap_or_else(|err| exit_with_error(err));
+                tournament::print_round_robin(&round_robin);
+            }
+        }
+        Command::Analyse { input, options } => {
+            let day2 = Day2::new(options.rules, &options.cipher);
+            let reports = load_guide(&input, options.verbose)
+                .and_then(|guide| day2.part1.decode(&day2.rules, &guide))
+                .and_then(|rounds| {
+                    let opponent: Vec<usize> = rounds
+                        .into_iter()
+                        .map(|round| round.opponent_shape)
+                        .collect();
+                    analysis::analyse(&day2.rules, &opponent)
+                })
+                .unwrap_or_else(|err| exit_with_error(err));
+            analysis::print_table(&reports);
+        }
+        Command::Crack {
+            input,
+            goal,
+            options,
+        } => {
+            let base = Cipher::shapes(&options.rules).with_override(&options.cipher, Part::One);
+            let candidates = load_guide(&input, options.verbose)
+                .and_then(|guide| crack::candidates(&options.rules, &guide, &base))
+                .unwrap_or_else(|err| exit_with_error(err));
+            let chosen = crack::best(&candidates, goal);
+            crack::print_table(&candidates, chosen);
+            if chosen.is_none() {
+                exit_with_error("no reading of the second column meets the target");
+            }
+        }
+        Command::Repack { input, options } => {
+            let plans = input
+                .load_normalized(3, options.verbose)
+                .and_then(|input| {
+                    day3::parse_inventory(input.text(), &options.priorities)
+                        .map_err(|err| input.remap(err))
+                })
+                .map(|inventory| repack::plan_all(&inventory))
+                .unwrap_or_else(|err| exit_with_error(err));
+            repack::print_plans(&plans);
+        }
+        Command::Generate {
+            day,
+            seed,
+            settings,
+            output,
+            answers,
+        } => {
+            let generated =
+                generate::generate(day, seed, &settings).unwrap_or_else(|err| exit_with_error(err));
+            let write = |path: &Path, content: &str| {
+                fs::write(path, content).unwrap_or_else(|err| {
+                    exit_with_error(format!("could not write `{}`: {}", path.display(), err))
+                })
+            };
+            match output {
+                Some(path) => write(&path, &generated.input),
+                None => print!("{}", generated.input),
+            }
+            if let Some(path) = answers {
+                write(&path, &generate::answers_file(day, seed, &generated));
+            }
+        }
+        Command::Validate {
+            day,
+            part,
+            input,
+            options,
+        } => {
+            let solution = solution::find(day, &options).expect("day was validated by the parser");
+            let normalized = input
+                .load_normalized(day, options.verbose)
+                .unwrap_or_else(|err| exit_with_error(err));
+            let violations: Vec<AocError> = solution
+                .validate(normalized.text())
+                .into_iter()
+                .map(|violation| normalized.remap(violation))
+                .collect();
+            let path = input.path(day);
+            for violation in &violations {
+                println!("{}: {}", path.display(), violation);
+            }
+            if !violations.is_empty() {
+                exit_with_error(format!(
+                    "day {} input breaks the puzzle guarantees {} times",
+                    day,
+                    violations.len()
+                ));
+            }
+            println!("{}: no violations\n", path.display());
+
+            if !runner::run_loaded(day, part, &normalized, &options).succeeded {
+                process::exit(1);
+            }
+        }
+        Command::Run {
+            days,
+            part,
+            input,
+            options,
+            time,
+            json,
+        } => {
+            let report = runner::run(&days, part, &input, &options);
+            if time {
+                println!();
+                timing::print_table(&report.timings);
+            }
+            if let Some(path) = json {
+                if let Err(err) = timing::write_json(&path, &report.timings) {
+                    exit_with_error(format!("could not write `{}`: {}", path.display(), err));
+                }
+            }
+            if !report.succeeded {
+                process::exit(1);
+            }
+        }
+        Command::Verify {
+            days,
+            part,
+            input,
+            options,
+            answers,
+        } => {
+            let answers = Answers::load(&answers).unwrap_or_else(|err| exit_with_error(err));
+            let checks = verify::verify(&days, part, &input, &options, &answers);
+            verify::print_table(&checks);
+            if checks
+                .iter()
+                .any(|check| matches!(check.status(), Status::Fail | Status::Error))
+            {
+                process::exit(1);
+            }
+        }
+    }
 }
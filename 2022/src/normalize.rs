@@ -0,0 +1,261 @@
+use std::{fmt, path::Path};
+
+use crate::error::AocError;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// A clean-up applied to an input before parsing, with how often it applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    ByteOrderMark,
+    CarriageReturns(usize),
+    TrailingWhitespace(usize),
+    /// Blank lines following another blank line.
+    RepeatedBlankLines(usize),
+    /// Blank lines before the first or after the last non-blank line.
+    OuterBlankLines(usize),
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Normalization::ByteOrderMark => write!(f, "stripped a byte order mark"),
+            Normalization::CarriageReturns(lines) => {
+                write!(f, "converted {} CRLF line endings", lines)
+            }
+            Normalization::TrailingWhitespace(lines) => {
+                write!(f, "trimmed trailing whitespace on {} lines", lines)
+            }
+            Normalization::RepeatedBlankLines(lines) => {
+                write!(f, "collapsed {} repeated blank lines", lines)
+            }
+            Normalization::OuterBlankLines(lines) => {
+                write!(f, "dropped {} blank lines at the start or end", lines)
+            }
+        }
+    }
+}
+
+/// An input after [`normalize`], remembering where each of its lines came
+/// from so parse errors still point into the original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized {
+    text: String,
+    applied: Vec<Normalization>,
+    original_lines: Vec<usize>,
+}
+
+impl Normalized {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn applied(&self) -> &[Normalization] {
+        &self.applied
+    }
+
+    /// The 1-based line of the original text that `line` of the normalized
+    /// text came from.
+    pub fn original_line(&self, line: usize) -> usize {
+        match line.checked_sub(1) {
+            Some(index) => self.original_lines.get(index).copied().unwrap_or(line),
+            None => line,
+        }
+    }
+
+    /// Points a parse error of the normalized text back into the original.
+    pub fn remap(&self, err: AocError) -> AocError {
+        match err {
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => AocError::parse(self.original_line(line), column, message),
+            AocError::UnknownItem { line, column, item } => AocError::UnknownItem {
+                line: self.original_line(line),
+                column,
+                item,
+            },
+            err => err,
+        }
+    }
+}
+
+/// Prints each normalization applied to the input read from `source` on stderr.
+pub fn report(source: &Path, applied: &[Normalization]) {
+    for normalization in applied {
+        eprintln!("note: {}: {}", source.display(), normalization);
+    }
+}
+
+/// Trims what no puzzle line ever needs: a CR of a CRLF ending and any
+/// trailing whitespace.
+pub fn trim_line(line: &str) -> &str {
+    line.trim_end()
+}
+
+/// The non-blank lines of `input` with their 1-based line numbers, for the
+/// days whose inputs have no use for blank lines: only day 1 separates its
+/// elves with them.
+pub fn non_blank_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// A non-blank line left by [`LineNormalizer::push`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// Whether one or more blank lines came between this and the previous line.
+    pub after_separator: bool,
+}
+
+/// The line-by-line core of [`normalize`], for readers that never hold the
+/// whole input.
+#[derive(Debug, Default)]
+pub struct LineNormalizer {
+    lines: usize,
+    byte_order_mark: bool,
+    carriage_returns: usize,
+    trailing_whitespace: usize,
+    repeated_blank_lines: usize,
+    outer_blank_lines: usize,
+    pending_blank_lines: usize,
+    seen_text: bool,
+}
+
+impl LineNormalizer {
+    pub fn new() -> LineNormalizer {
+        LineNormalizer::default()
+    }
+
+    /// Cleans the next raw line, with or without its line ending. Blank
+    /// lines give `None` and mark the next line as following a separator.
+    pub fn push<'a>(&mut self, line: &'a str) -> Option<Line<'a>> {
+        self.lines += 1;
+        let line = match line.strip_prefix(BYTE_ORDER_MARK) {
+            Some(line) if self.lines == 1 => {
+                self.byte_order_mark = true;
+                line
+            }
+            _ => line,
+        };
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = match line.strip_suffix('\r') {
+            Some(line) => {
+                self.carriage_returns += 1;
+                line
+            }
+            None => line,
+        };
+        let trimmed = trim_line(line);
+        if trimmed.len() != line.len() {
+            self.trailing_whitespace += 1;
+        }
+
+        if trimmed.is_empty() {
+            self.pending_blank_lines += 1;
+            return None;
+        }
+        let after_separator = self.pending_blank_lines > 0 && self.seen_text;
+        if after_separator {
+            self.repeated_blank_lines += self.pending_blank_lines - 1;
+        } else {
+            self.outer_blank_lines += self.pending_blank_lines;
+        }
+        self.pending_blank_lines = 0;
+        self.seen_text = true;
+        Some(Line {
+            text: trimmed,
+            after_separator,
+        })
+    }
+
+    /// The normalizations applied to the lines pushed so far, counting any
+    /// blank lines left pending as trailing ones.
+    pub fn applied(&self) -> Vec<Normalization> {
+        let mut applied = Vec::new();
+        if self.byte_order_mark {
+            applied.push(Normalization::ByteOrderMark);
+        }
+        if self.carriage_returns > 0 {
+            applied.push(Normalization::CarriageReturns(self.carriage_returns));
+        }
+        if self.trailing_whitespace > 0 {
+            applied.push(Normalization::TrailingWhitespace(self.trailing_whitespace));
+        }
+        if self.repeated_blank_lines > 0 {
+            applied.push(Normalization::RepeatedBlankLines(self.repeated_blank_lines));
+        }
+        let outer_blank_lines = self.outer_blank_lines + self.pending_blank_lines;
+        if outer_blank_lines > 0 {
+            applied.push(Normalization::OuterBlankLines(outer_blank_lines));
+        }
+        applied
+    }
+}
+
+/// Strips a byte order mark, CRLF endings and trailing whitespace, and
+/// reduces every run of blank lines to a single separator, dropping those
+/// before the first and after the last line.
+pub fn normalize(input: &str) -> Normalized {
+    let mut normalizer = LineNormalizer::new();
+    let mut text = String::with_capacity(input.len());
+    let mut original_lines = Vec::new();
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        if let Some(line) = normalizer.push(line) {
+            if line.after_separator {
+                text.push('\n');
+                original_lines.push(index);
+            }
+            text.push_str(line.text);
+            text.push('\n');
+            original_lines.push(index + 1);
+        }
+    }
+
+    Normalized {
+        text,
+        applied: normalizer.applied(),
+        original_lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_leaves_clean_input_alone() {
+        let normalized = normalize("1\n2\n\n3\n");
+        assert_eq!(normalized.text(), "1\n2\n\n3\n");
+        assert!(normalized.applied().is_empty());
+    }
+
+    #[test]
+    fn normalize_reports_each_clean_up() {
+        let normalized = normalize("\u{feff}\r\n1 \r\n2\r\n\r\n \n\n3\t\n\n");
+        assert_eq!(normalized.text(), "1\n2\n\n3\n");
+        assert_eq!(
+            normalized.applied(),
+            [
+                Normalization::ByteOrderMark,
+                Normalization::CarriageReturns(4),
+                Normalization::TrailingWhitespace(3),
+                Normalization::RepeatedBlankLines(2),
+                Normalization::OuterBlankLines(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn remap_points_at_original_lines() {
+        let normalized = normalize("\n1\n\n\n\nx\n");
+        assert_eq!(normalized.text(), "1\n\nx\n");
+        let err = normalized.remap(AocError::parse(3, 1, "bad"));
+        assert_eq!(err.to_string(), "line 6, column 1: bad");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    input::Input,
+    normalize::Normalized,
+    solution::{self, DynSolution, Options, Part},
+    timing::{self, Measurement},
+};
+
+#[derive(Debug)]
+pub struct Report {
+    pub succeeded: bool,
+    pub timings: Vec<Measurement>,
+}
+
+/// Runs the selected days, reporting every failure without stopping.
+pub fn run(days: &[u8], part: Option<Part>, input: &Input, options: &Options) -> Report {
+    let mut report = Report {
+        succeeded: true,
+        timings: Vec::new(),
+    };
+    for &day in days {
+        let solution = solution::find(day, options).expect("day was validated by the parser");
+        println!("--- Day {}: {} ---", day, solution.title());
+        let (input, elapsed) = timing::time(|| input.load_normalized(day, options.verbose));
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: day {}: {}", day, err);
+                report.succeeded = false;
+                continue;
+            }
+        };
+        report
+            .timings
+            .push(Measurement::once(format!("day {} input", day), elapsed));
+        solve(solution.as_ref(), part, &input, &mut report);
+    }
+    report
+}
+
+/// Runs `day` on an input that is already loaded, such as one `validate`
+/// has checked, so that stdin is not read a second time.
+pub fn run_loaded(day: u8, part: Option<Part>, input: &Normalized, options: &Options) -> Report {
+    let mut report = Report {
+        succeeded: true,
+        timings: Vec::new(),
+    };
+    let solution = solution::find(day, options).expect("day was validated by the parser");
+    println!("--- Day {}: {} ---", day, solution.title());
+    solve(solution.as_ref(), part, input, &mut report);
+    report
+}
+
+/// Parses `input` and solves the selected parts, adding to `report`.
+fn solve(solution: &dyn DynSolution, part: Option<Part>, input: &Normalized, report: &mut Report) {
+    let day = solution.day();
+    let (parsed, elapsed) =
+        timing::time(|| solution.parse(input.text()).map_err(|err| input.remap(err)));
+    report
+        .timings
+        .push(Measurement::once(format!("day {} parse", day), elapsed));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("error: day {}: {}", day, err);
+            report.succeeded = false;
+            return;
+        }
+    };
+    for part in Part::selected(part) {
+        let (answer, elapsed) = timing::time(|| parsed.solve(part));
+        match answer {
+            Ok(answer) => println!("Part {}: {}", part, answer),
+            Err(err) => {
+                eprintln!("error: day {} part {}: {}", day, part, err);
+                report.succeeded = false;
+            }
+        }
+        report.timings.push(Measurement::once(
+            format!("day {} part {}", day, part),
+            elapsed,
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt;
+
+use crate::{
+    day1::Day1,
+    day2::{cipher::CipherOverride, rules::Rules, Day2},
+    day3::{priorities::Priorities, Day3, Grouping},
+    error::{AocError, Result},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The parts to run when `selected` is the optional `--part` filter.
+    pub fn selected(selected: Option<Part>) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|part| selected.is_none_or(|selected| selected == *part))
+            .collect()
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// A single day of the calendar. The input is parsed once into a `Model`
+/// that both parts then share.
+pub trait Solution: Sync {
+    type Model;
+
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Model>;
+
+    fn part1(&self, model: &Self::Model) -> Result<String>;
+
+    fn part2(&self, model: &Self::Model) -> Result<String>;
+
+    /// Every way `input` breaks the guarantees of the puzzle story, where
+    /// parsing stops at the first. By default, just that first error.
+    fn validate(&self, input: &str) -> Vec<AocError> {
+        self.parse(input).err().into_iter().collect()
+    }
+
+    fn solve(&self, part: Part, model: &Self::Model) -> Result<String> {
+        match part {
+            Part::One => self.part1(model),
+            Part::Two => self.part2(model),
+        }
+    }
+}
+
+/// Object-safe view of a [`Solution`], with the model type erased so that
+/// every day fits in the registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>>;
+
+    fn validate(&self, input: &str) -> Vec<AocError>;
+}
+
+/// A parsed input, ready to solve either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<String>;
+}
+
+struct ParsedModel<'a, S: Solution> {
+    solution: &'a S,
+    model: S::Model,
+}
+
+impl<S: Solution> Parsed for ParsedModel<'_, S> {
+    fn solve(&self, part: Part) -> Result<String> {
+        self.solution.solve(part, &self.model)
+    }
+}
+
+impl<S: Solution> DynSolution for S {
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>> {
+        let model = Solution::parse(self, input)?;
+        Ok(Box::new(ParsedModel {
+            solution: self,
+            model,
+        }))
+    }
+
+    fn validate(&self, input: &str) -> Vec<AocError> {
+        Solution::validate(self, input)
+    }
+}
+
+/// Tunable parameters of the solvers. The defaults answer the puzzles as asked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// How many elves day 1 part 2 adds up.
+    pub top_n: usize,
+    /// Report every clean-up applied to the inputs on stderr.
+    pub verbose: bool,
+    /// The day 2 game.
+    pub rules: Rules,
+    /// Day 2 guide columns that replace those of the puzzle.
+    pub cipher: CipherOverride,
+    /// How day 3 part 2 groups the rucksacks.
+    pub grouping: Grouping,
+    /// The day 3 item types and their priorities.
+    pub priorities: Priorities,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            top_n: Day1::default().top_n,
+            verbose: false,
+            rules: Rules::default(),
+            cipher: CipherOverride::default(),
+            grouping: Grouping::default(),
+            priorities: Priorities::default(),
+        }
+    }
+}
+
+pub fn solutions(options: &Options) -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(Day1 {
+            top_n: options.top_n,
+        }),
+        Box::new(Day2::new(options.rules.clone(), &options.cipher)),
+        Box::new(Day3 {
+            grouping: options.grouping,
+            priorities: options.priorities.clone(),
+        }),
+    ]
+}
+
+pub fn days() -> Vec<u8> {
+    solutions(&Options::default())
+        .iter()
+        .map(|solution| solution.day())
+        .collect()
+}
+
+pub fn find(day: u8, options: &Options) -> Option<Box<dyn DynSolution>> {
+    solutions(options)
+        .into_iter()
+        .find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_day_solves_its_example() {
+        let examples = [
+            (1, include_str!("day1/example.txt"), "24000", "45000"),
+            (2, include_str!("day2/example.txt"), "15", "12"),
+            (3, include_str!("day3/example.txt"), "157", "70"),
+        ];
+        for (day, input, part1, part2) in examples {
+            let solution = find(day, &Options::default()).unwrap();
+            let parsed = solution.parse(input).unwrap();
+            assert_eq!(parsed.solve(Part::One).unwrap(), part1, "day {}", day);
+            assert_eq!(parsed.solve(Part::Two).unwrap(), part2, "day {}", day);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    fs,
+    hint::black_box,
+    io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub name: String,
+    pub iterations: u32,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Measurement {
+    pub fn once(name: impl Into<String>, duration: Duration) -> Measurement {
+        Measurement {
+            name: name.into(),
+            iterations: 1,
+            mean: duration,
+            min: duration,
+            max: duration,
+        }
+    }
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Runs `f` repeatedly for roughly `budget` after a single warm-up call,
+/// and at least `min_iterations` times.
+pub fn measure<T>(
+    name: &str,
+    budget: Duration,
+    min_iterations: u32,
+    mut f: impl FnMut() -> T,
+) -> Measurement {
+    let (_, warm_up) = time(|| black_box(f()));
+    let iterations = (budget.as_nanos() / warm_up.as_nanos().max(1))
+        .clamp(min_iterations as u128, u32::MAX as u128) as u32;
+
+    let mut total = Duration::ZERO;
+    let mut min = Duration::MAX;
+    let mut max = Duration::ZERO;
+    for _ in 0..iterations {
+        let (_, elapsed) = time(|| black_box(f()));
+        total += elapsed;
+        min = min.min(elapsed);
+        max = max.max(elapsed);
+    }
+    Measurement {
+        name: name.to_string(),
+        iterations,
+        mean: total / iterations,
+        min,
+        max,
+    }
+}
+
+pub fn print_table(measurements: &[Measurement]) {
+    let name_width = measurements
+        .iter()
+        .map(|measurement| measurement.name.len())
+        .chain(["Name".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:<nw$}  {:>8}  {:>10}  {:>10}  {:>10}",
+        "Name",
+        "Runs",
+        "Mean",
+        "Min",
+        "Max",
+        nw = name_width
+    );
+    for measurement in measurements {
+        println!(
+            "{:<nw$}  {:>8}  {:>10}  {:>10}  {:>10}",
+            measurement.name,
+            measurement.iterations,
+            format!("{:.2?}", measurement.mean),
+            format!("{:.2?}", measurement.min),
+            format!("{:.2?}", measurement.max),
+            nw = name_width
+        );
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let entries: Vec<String> = measurements
+        .iter()
+        .map(|measurement| {
+            format!(
+                "    {{\"name\": \"{}\", \"iterations\": {}, \"mean_ns\": {}, \"min_ns\": {}, \"max_ns\": {}}}",
+                escape_json(&measurement.name),
+                measurement.iterations,
+                measurement.mean.as_nanos(),
+                measurement.min.as_nanos(),
+                measurement.max.as_nanos()
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"measurements\": [\n{}\n  ]\n}}\n",
+        entries.join(",\n")
+    )
+}
+
+pub fn write_json(path: &Path, measurements: &[Measurement]) -> io::Result<()> {
+    fs::write(path, to_json(measurements))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_json_escapes_names() {
+        let json = to_json(&[Measurement::once("a \"b\"", Duration::from_nanos(42))]);
+        assert!(json.contains(r#""name": "a \"b\"""#), "{}", json);
+        assert!(json.contains(r#""mean_ns": 42"#), "{}", json);
+    }
+
+    #[test]
+    fn measure_runs_at_least_min_iterations() {
+        let mut calls = 0;
+        let measurement = measure("noop", Duration::ZERO, 5, || calls += 1);
+        assert_eq!(measurement.iterations, 5);
+        assert_eq!(calls, 6);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    answers::Answers,
+    input::Input,
+    solution::{self, Options, Part},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// The solver returned an error instead of an answer.
+    Error,
+    /// No answer is recorded for this part yet.
+    Missing,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub expected: Option<String>,
+    /// The computed answer, or the message of the error that prevented it.
+    pub actual: std::result::Result<String, String>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.actual) {
+            (_, Err(_)) => Status::Error,
+            (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+            (Some(_), Ok(_)) => Status::Fail,
+            (None, Ok(_)) => Status::Missing,
+        }
+    }
+
+    fn actual_text(&self) -> &str {
+        match &self.actual {
+            Ok(actual) => actual,
+            Err(_) => "-",
+        }
+    }
+}
+
+/// Checks every selected part. A day whose input cannot be loaded or parsed
+/// gets an error for each part, and the other days are still checked.
+pub fn verify(
+    days: &[u8],
+    part: Option<Part>,
+    input: &Input,
+    options: &Options,
+    answers: &Answers,
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for &day in days {
+        let solution = solution::find(day, options).expect("day was validated by the parser");
+        let parsed = input
+            .load_normalized(day, options.verbose)
+            .and_then(|input| solution.parse(input.text()).map_err(|err| input.remap(err)));
+        for part in Part::selected(part) {
+            let actual = match &parsed {
+                Ok(parsed) => parsed.solve(part).map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            checks.push(Check {
+                day,
+                part,
+                expected: answers.get(day, part).map(str::to_string),
+                actual,
+            });
+        }
+    }
+    checks
+}
+
+pub fn print_table(checks: &[Check]) {
+    let expected_width = checks
+        .iter()
+        .filter_map(|check| check.expected.as_ref().map(String::len))
+        .chain(["Expected".len()])
+        .max()
+        .unwrap_or_default();
+    let actual_width = checks
+        .iter()
+        .map(|check| check.actual_text().len())
+        .chain(["Actual".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "Day  Part  {:>ew$}  {:>aw$}  Status",
+        "Expected",
+        "Actual",
+        ew = expected_width,
+        aw = actual_width
+    );
+    for check in checks {
+        let status = match check.status() {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Error => "ERROR",
+            Status::Missing => "missing",
+        };
+        println!(
+            "{:>3}  {:>4}  {:>ew$}  {:>aw$}  {}",
+            check.day,
+            check.part,
+            check.expected.as_deref().unwrap_or("-"),
+            check.actual_text(),
+            status,
+            ew = expected_width,
+            aw = actual_width
+        );
+    }
+
+    for check in checks {
+        if let Err(err) = &check.actual {
+            eprintln!("error: day {} part {}: {}", check.day, check.part, err);
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|check| matches!(check.status(), Status::Fail | Status::Error))
+        .count();
+    println!("\n{} checked, {} failed", checks.len(), failed);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+
+    #[test]
+    fn missing_input_is_an_error_for_that_day_only() {
+        let dir = env::temp_dir().join(format!("aoc-verify-{}", process::id()));
+        fs::create_dir_all(dir.join("day2")).unwrap();
+        fs::write(dir.join("day2/input.txt"), include_str!("day2/example.txt")).unwrap();
+        let answers = Answers::parse("2 1 15\n2 2 12\n").unwrap();
+
+        let checks = verify(
+            &[1, 2],
+            None,
+            &Input::Directory(dir.clone()),
+            &Options::default(),
+            &answers,
+        );
+        fs::remove_dir_all(&dir).unwrap();
+        let statuses: Vec<(u8, Status)> = checks
+            .iter()
+            .map(|check| (check.day, check.status()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, Status::Error),
+                (1, Status::Error),
+                (2, Status::Pass),
+                (2, Status::Pass)
+            ]
+        );
+    }
+}
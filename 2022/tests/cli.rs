@@ -0,0 +1,49 @@
+//! Runs the `aoc` binary, for what only shows across a whole process, such
+//! as how often stdin is read.
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("aoc starts");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin.as_bytes())
+        .expect("aoc reads stdin");
+    child.wait_with_output().expect("aoc finishes")
+}
+
+#[test]
+fn validate_solves_the_stdin_it_checked() {
+    let output = run(
+        &["validate", "2", "-"],
+        include_str!("../src/day2/example.txt"),
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("<stdin>: no violations"), "{}", stdout);
+    assert!(stdout.contains("Part 1: 15\nPart 2: 12\n"), "{}", stdout);
+}
+
+#[test]
+fn validate_rejects_an_empty_input() {
+    let output = run(&["validate", "3", "-"], "\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success());
+    assert!(
+        stdout.contains("<stdin>: invalid puzzle data: there are no rucksacks"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Part 1"), "{}", stdout);
+}